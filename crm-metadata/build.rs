@@ -4,6 +4,7 @@ use proto_builder_trait::tonic::BuilderAttributes;
 use std::fs;
 
 fn main() -> Result<()> {
+    println!("cargo:rerun-if-changed=migrations");
     fs::create_dir_all("src/pb")?;
     let builder = tonic_build::configure();
     builder
@@ -11,6 +12,23 @@ fn main() -> Result<()> {
         .with_derive_builder(&["Content", "Publisher"], None)
         .with_sqlx_from_row(&["Content"], None)
         .with_type_attributes(&["MaterializeRequest"], &[r#"#[derive(Eq, Hash)]"#])
+        // details are stored as jsonb and handed to templates, tagged with their kind
+        .with_serde(
+            &["VlogDetails", "MovieDetails", "AiGeneratedDetails"],
+            true,
+            true,
+            None,
+        )
+        .with_serde(
+            &["Content.details"],
+            true,
+            true,
+            Some(&[r#"#[serde(tag = "kind", rename_all = "snake_case")]"#]),
+        )
+        .with_type_attributes(
+            &["MaterializeResponse.result", "CatalogRecord.record"],
+            &[r#"#[allow(clippy::large_enum_variant)]"#],
+        )
         .compile_protos(
             &[
                 "../protos/crm_metadata/messages.proto",
@@ -0,0 +1,29 @@
+-- content catalog backing the Metadata service
+create type content_type as enum('unspecified', 'short', 'vlog', 'movie', 'ai_generated');
+create table if NOT EXISTS publishers(
+  id serial NOT NULL PRIMARY KEY,
+  name varchar(64) NOT NULL,
+  avatar varchar(256) NOT NULL DEFAULT '',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create table if NOT EXISTS contents(
+  id serial NOT NULL PRIMARY KEY,
+  name varchar(128) NOT NULL,
+  description text NOT NULL DEFAULT '',
+  url varchar(256) NOT NULL DEFAULT '',
+  images varchar(256) NOT NULL DEFAULT '',
+  content_type content_type NOT NULL DEFAULT 'unspecified',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  views bigint NOT NULL DEFAULT 0,
+  likes bigint NOT NULL DEFAULT 0,
+  dislikes bigint NOT NULL DEFAULT 0
+);
+create table if NOT EXISTS content_publishers(
+  content_id int NOT NULL REFERENCES contents(id) ON DELETE CASCADE,
+  publisher_id int NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+  PRIMARY KEY(content_id, publisher_id)
+);
+CREATE index if NOT EXISTS contents_content_type_idx ON contents(content_type);
+CREATE index if NOT EXISTS contents_created_at_idx ON contents(created_at);
+CREATE index if NOT EXISTS content_publishers_publisher_id_idx ON content_publishers(publisher_id);
@@ -0,0 +1,4 @@
+-- keyset pagination for ListContents
+CREATE index if NOT EXISTS contents_views_idx ON contents(views DESC, id DESC);
+CREATE index if NOT EXISTS contents_likes_idx ON contents(likes DESC, id DESC);
+CREATE index if NOT EXISTS contents_recent_idx ON contents(created_at DESC, id DESC);
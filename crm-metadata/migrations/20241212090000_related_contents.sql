@@ -0,0 +1,9 @@
+-- top-k co-watch neighbors per content, rebuilt by the similarity job
+create table if NOT EXISTS related_contents(
+  content_id int NOT NULL,
+  related_id int NOT NULL,
+  score double precision NOT NULL,
+  computed_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY(content_id, related_id)
+);
+CREATE index if NOT EXISTS related_contents_score_idx ON related_contents(content_id, score DESC);
@@ -0,0 +1,10 @@
+-- per day engagement counters, for trending over a window instead of lifetime totals
+create table if NOT EXISTS content_daily_stats(
+  content_id int NOT NULL REFERENCES contents(id) ON DELETE CASCADE,
+  day date NOT NULL,
+  views bigint NOT NULL DEFAULT 0,
+  likes bigint NOT NULL DEFAULT 0,
+  dislikes bigint NOT NULL DEFAULT 0,
+  PRIMARY KEY(content_id, day)
+);
+CREATE index if NOT EXISTS content_daily_stats_day_idx ON content_daily_stats(day);
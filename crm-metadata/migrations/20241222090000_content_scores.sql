@@ -0,0 +1,18 @@
+-- lower bound of the 95% wilson score interval, content with few votes ranks below well rated popular ones
+CREATE OR REPLACE FUNCTION wilson_lower_bound(likes bigint, dislikes bigint) RETURNS double precision AS $$
+  SELECT CASE WHEN likes + dislikes = 0 THEN 0 ELSE
+    (p + z * z / (2 * n) - z * sqrt((p * (1 - p) + z * z / (4 * n)) / n)) / (1 + z * z / n)
+  END
+  FROM (SELECT likes::float8 / (likes + dislikes) AS p, (likes + dislikes)::float8 AS n, 1.96::float8 AS z) s
+$$ LANGUAGE sql IMMUTABLE;
+
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS rating double precision GENERATED ALWAYS AS (wilson_lower_bound(likes, dislikes)) STORED;
+
+CREATE index if NOT EXISTS contents_rating_idx ON contents(rating DESC, id DESC);
+
+-- views of the last 14 days, halving every 3 days
+CREATE OR REPLACE FUNCTION content_trending(content_id int) RETURNS double precision AS $$
+  SELECT coalesce(sum(d.views * power(0.5, ((now() at time zone 'utc')::date - d.day) / 3.0)), 0)::float8
+  FROM content_daily_stats d
+  WHERE d.content_id = $1 AND d.day > (now() at time zone 'utc')::date - 14
+$$ LANGUAGE sql STABLE;
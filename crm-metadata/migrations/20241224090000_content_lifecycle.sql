@@ -0,0 +1,14 @@
+-- publication lifecycle, only published content inside its window may be promoted
+create type content_status as enum('draft', 'published', 'archived');
+-- the catalog so far is live, new content starts as a draft unless created otherwise
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS status content_status NOT NULL DEFAULT 'published';
+ALTER TABLE contents ALTER COLUMN status SET DEFAULT 'draft';
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS publish_at timestamptz;
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS expire_at timestamptz;
+CREATE index if NOT EXISTS contents_status_idx ON contents(status);
+
+CREATE OR REPLACE FUNCTION content_available(c contents) RETURNS boolean AS $$
+  SELECT c.status = 'published'
+    AND coalesce(c.publish_at <= now(), true)
+    AND coalesce(c.expire_at > now(), true)
+$$ LANGUAGE sql STABLE;
@@ -0,0 +1,2 @@
+-- type specific details, the Content.details oneof tagged with its kind
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS details jsonb;
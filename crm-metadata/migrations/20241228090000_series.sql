@@ -0,0 +1,14 @@
+-- series group contents into seasons and episodes, a content is in at most one series
+create table if NOT EXISTS series(
+  id serial NOT NULL PRIMARY KEY,
+  name varchar(128) NOT NULL,
+  description text NOT NULL DEFAULT '',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create table if NOT EXISTS series_episodes(
+  series_id int NOT NULL REFERENCES series(id) ON DELETE CASCADE,
+  season int NOT NULL,
+  episode int NOT NULL,
+  content_id int NOT NULL UNIQUE REFERENCES contents(id) ON DELETE CASCADE,
+  PRIMARY KEY(series_id, season, episode)
+);
@@ -0,0 +1,14 @@
+-- tag taxonomy, genres are tags of their own kind so they can be listed apart
+create type tag_kind as enum('tag', 'genre');
+create table if NOT EXISTS tags(
+  id serial NOT NULL PRIMARY KEY,
+  name varchar(64) NOT NULL UNIQUE,
+  kind tag_kind NOT NULL DEFAULT 'tag',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create table if NOT EXISTS content_tags(
+  content_id int NOT NULL REFERENCES contents(id) ON DELETE CASCADE,
+  tag_id int NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+  PRIMARY KEY(content_id, tag_id)
+);
+CREATE index if NOT EXISTS content_tags_tag_id_idx ON content_tags(tag_id);
@@ -0,0 +1,7 @@
+-- image variants replace the single images url, which becomes the thumbnail
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS image_variants jsonb NOT NULL DEFAULT '[]';
+UPDATE contents SET image_variants = jsonb_build_array(jsonb_build_object(
+  'role', 'thumbnail', 'url', images, 'width', 0, 'height', 0, 'format', 'unspecified'))
+WHERE images <> '';
+ALTER TABLE contents DROP COLUMN images;
+ALTER TABLE contents RENAME COLUMN image_variants TO images;
@@ -0,0 +1,36 @@
+-- change log of contents and publishers, watched by downstream caches and resumed by seq
+create type catalog_entity as enum('content', 'publisher');
+create type change_kind as enum('created', 'updated', 'deleted');
+create table if NOT EXISTS catalog_changes(
+  seq bigserial NOT NULL PRIMARY KEY,
+  entity catalog_entity NOT NULL,
+  entity_id int NOT NULL,
+  kind change_kind NOT NULL,
+  changed_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+create or replace function record_catalog_change() returns trigger as $$
+declare
+  row_id int;
+  change change_kind;
+  seq bigint;
+begin
+  -- serialize writers so seq follows commit order and a resumed watch can't skip a change
+  perform pg_advisory_xact_lock(hashtext('catalog_changes'));
+  row_id := case when TG_OP = 'DELETE' then old.id else new.id end;
+  change := case TG_OP when 'INSERT' then 'created' when 'UPDATE' then 'updated' else 'deleted' end;
+  insert into catalog_changes(entity, entity_id, kind)
+    values(case TG_TABLE_NAME when 'contents' then 'content' else 'publisher' end::catalog_entity, row_id, change)
+    returning catalog_changes.seq into seq;
+  perform pg_notify('catalog_changes', seq::text);
+  return null;
+end;
+$$ language plpgsql;
+
+create trigger contents_changes after insert or delete on contents
+  for each row execute function record_catalog_change();
+-- counters are bumped all the time without touching updated_at, they aren't changes to watch
+create trigger contents_updates after update on contents
+  for each row when (new.updated_at is distinct from old.updated_at) execute function record_catalog_change();
+create trigger publishers_changes after insert or update or delete on publishers
+  for each row execute function record_catalog_change();
@@ -0,0 +1,8 @@
+-- users follow publishers, keyed by email like the rest of the user data
+create table if NOT EXISTS publisher_follows(
+  email varchar(128) NOT NULL,
+  publisher_id int NOT NULL REFERENCES publishers(id) ON DELETE CASCADE,
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY(email, publisher_id)
+);
+CREATE index if NOT EXISTS publisher_follows_publisher_id_idx ON publisher_follows(publisher_id);
@@ -0,0 +1,8 @@
+-- trending kept on the content and refreshed when events are flushed, instead of summing the daily stats per row
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS trending double precision NOT NULL DEFAULT 0;
+-- the utc day trending was computed on, it decays by the day so older values get recomputed
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS trending_day date NOT NULL DEFAULT (now() at time zone 'utc')::date;
+
+UPDATE contents SET trending = content_trending(id);
+
+CREATE index if NOT EXISTS contents_trending_idx ON contents(trending DESC, id DESC);
@@ -0,0 +1,16 @@
+-- when the content first went live, drafts are created long before they are published
+ALTER TABLE contents ADD COLUMN IF NOT EXISTS published_at timestamptz;
+UPDATE contents SET published_at = created_at WHERE status = 'published' AND published_at IS NULL;
+
+-- every way into 'published' sets it once, publishing again with a new window keeps it
+create or replace function set_published_at() returns trigger as $$
+begin
+  if new.status = 'published' and new.published_at is null then
+    new.published_at := now();
+  end if;
+  return new;
+end;
+$$ language plpgsql;
+
+create trigger contents_published_at before insert or update of status on contents
+  for each row execute function set_published_at();
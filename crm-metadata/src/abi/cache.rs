@@ -0,0 +1,118 @@
+use std::{
+    num::NonZeroUsize,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use lru::LruCache;
+use metrics::counter;
+
+use crate::{config::CacheConfig, pb::Content};
+
+/// contents served by Materialize, dropped when they expire or the catalog changes them
+pub(crate) struct ContentCache {
+    // none when caching is disabled
+    entries: Option<Mutex<LruCache<u32, Entry>>>,
+    ttl: Duration,
+}
+
+struct Entry {
+    content: Content,
+    expires_at: Instant,
+}
+
+impl ContentCache {
+    pub(crate) fn new(config: &CacheConfig) -> Self {
+        ContentCache {
+            entries: NonZeroUsize::new(config.capacity).map(|n| Mutex::new(LruCache::new(n))),
+            ttl: Duration::from_secs(config.ttl),
+        }
+    }
+
+    pub(crate) fn get(&self, id: u32) -> Option<Content> {
+        let entries = self.entries.as_ref()?;
+        let mut entries = entries.lock().unwrap();
+        let content = match entries.get(&id) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.content.clone()),
+            Some(_) => {
+                entries.pop(&id);
+                None
+            }
+            None => None,
+        };
+        match content {
+            Some(_) => counter!("metadata_materialize_cache_hits_total").increment(1),
+            None => counter!("metadata_materialize_cache_misses_total").increment(1),
+        }
+        content
+    }
+
+    pub(crate) fn insert(&self, content: Content) {
+        if let Some(entries) = &self.entries {
+            let entry = Entry {
+                expires_at: Instant::now() + self.ttl,
+                content,
+            };
+            entries.lock().unwrap().put(entry.content.id, entry);
+        }
+    }
+
+    pub(crate) fn invalidate(&self, id: u32) {
+        if let Some(entries) = &self.entries {
+            entries.lock().unwrap().pop(&id);
+        }
+    }
+
+    /// for changes to publishers or tags, which show up in any number of contents
+    pub(crate) fn clear(&self) {
+        if let Some(entries) = &self.entries {
+            entries.lock().unwrap().clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(id: u32, name: &str) -> Content {
+        Content {
+            id,
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn content_cache_should_work() {
+        let cache = ContentCache::new(&CacheConfig {
+            capacity: 2,
+            ttl: 60,
+        });
+        cache.insert(content(1, "a"));
+        cache.insert(content(2, "b"));
+        assert_eq!(cache.get(1), Some(content(1, "a")));
+        // 2 is the least recently used
+        cache.insert(content(3, "c"));
+        assert_eq!(cache.get(2), None);
+        cache.invalidate(1);
+        assert_eq!(cache.get(1), None);
+        assert_eq!(cache.get(3), Some(content(3, "c")));
+        cache.clear();
+        assert_eq!(cache.get(3), None);
+
+        let cache = ContentCache::new(&CacheConfig {
+            capacity: 2,
+            ttl: 0,
+        });
+        cache.insert(content(1, "a"));
+        assert_eq!(cache.get(1), None);
+
+        let cache = ContentCache::new(&CacheConfig {
+            capacity: 0,
+            ttl: 60,
+        });
+        cache.insert(content(1, "a"));
+        assert_eq!(cache.get(1), None);
+    }
+}
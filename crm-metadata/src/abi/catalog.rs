@@ -0,0 +1,258 @@
+use futures::{Stream, StreamExt};
+use sqlx::{types::Json, PgPool};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{
+        catalog_record::Record, CatalogRecord, Content, ImportCatalogResponse, ImportError,
+        Publisher, Tag,
+    },
+    CatalogStream, MetaDataService, ServiceResult,
+};
+
+use super::{
+    content::{
+        content_status_name, content_type_name, internal, set_publishers, ContentRow,
+        CONTENT_SELECT,
+    },
+    details::check_details,
+    image::{check_images, ImageRow},
+    lifecycle::window,
+    tag::{set_tags, tag_kind_name, TagRow},
+};
+
+/// contents are exported in pages of this size
+const EXPORT_PAGE_SIZE: i64 = 500;
+
+impl MetaDataService {
+    /// every record is stored in its own transaction, a bad one is reported and skipped
+    pub async fn import_catalog(
+        &self,
+        mut stream: impl Stream<Item = Result<CatalogRecord, Status>> + Unpin,
+    ) -> ServiceResult<ImportCatalogResponse> {
+        let mut report = ImportCatalogResponse::default();
+        while let Some(record) = stream.next().await {
+            let record = record?;
+            let ret = match &record.record {
+                Some(Record::Publisher(publisher)) => {
+                    upsert_publisher(&self.pool, publisher).await.map(|_| {
+                        self.cache.clear();
+                        report.publishers += 1
+                    })
+                }
+                Some(Record::Tag(tag)) => upsert_tag(&self.pool, tag).await.map(|_| {
+                    self.cache.clear();
+                    report.tags += 1
+                }),
+                Some(Record::Content(content)) => {
+                    upsert_content(&self.pool, content).await.map(|id| {
+                        self.cache.invalidate(id);
+                        report.contents += 1
+                    })
+                }
+                None => Err("empty record".to_string()),
+            };
+            if let Err(reason) = ret {
+                report.errors.push(ImportError {
+                    line: record.line,
+                    reason,
+                });
+            }
+        }
+        Ok(Response::new(report))
+    }
+
+    pub async fn export_catalog(&self) -> ServiceResult<CatalogStream> {
+        let (tx, rx) = tokio::sync::mpsc::channel(1024);
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = export(&pool, &tx).await {
+                let _ = tx.send(Err(internal(e))).await;
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+async fn export(
+    pool: &PgPool,
+    tx: &tokio::sync::mpsc::Sender<Result<CatalogRecord, Status>>,
+) -> Result<(), sqlx::Error> {
+    let mut line = 0;
+    let publishers: Vec<(i32, String, String)> =
+        sqlx::query_as("select id, name, avatar from publishers order by id")
+            .fetch_all(pool)
+            .await?;
+    for (id, name, avatar) in publishers {
+        line += 1;
+        let publisher = Publisher {
+            id: id as _,
+            name,
+            avatar,
+        };
+        if send(tx, line, Record::Publisher(publisher)).await.is_err() {
+            return Ok(());
+        }
+    }
+
+    let tags =
+        sqlx::query_as::<_, TagRow>("select id, name, kind::text as kind from tags order by id")
+            .fetch_all(pool)
+            .await?;
+    for tag in tags {
+        line += 1;
+        if send(tx, line, Record::Tag(tag.into())).await.is_err() {
+            return Ok(());
+        }
+    }
+
+    let mut last_id = 0;
+    loop {
+        let rows = sqlx::query_as::<_, ContentRow>(&format!(
+            "{} where c.id > $1 group by c.id order by c.id limit $2",
+            CONTENT_SELECT
+        ))
+        .bind(last_id)
+        .bind(EXPORT_PAGE_SIZE)
+        .fetch_all(pool)
+        .await?;
+        let contents = rows.into_iter().map(Content::from).collect::<Vec<_>>();
+        let Some(last) = contents.last() else {
+            return Ok(());
+        };
+        last_id = last.id as i32;
+        for content in contents {
+            line += 1;
+            if send(tx, line, Record::Content(content)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// fails once the client is gone
+async fn send(
+    tx: &tokio::sync::mpsc::Sender<Result<CatalogRecord, Status>>,
+    line: u32,
+    record: Record,
+) -> Result<(), ()> {
+    let record = CatalogRecord {
+        line,
+        record: Some(record),
+    };
+    tx.send(Ok(record)).await.map_err(|_| ())
+}
+
+async fn upsert_publisher(pool: &PgPool, publisher: &Publisher) -> Result<(), String> {
+    if publisher.name.is_empty() {
+        return Err("publisher needs a name".to_string());
+    }
+    sqlx::query(
+        r#"insert into publishers(id, name, avatar) values(coalesce($1, nextval(pg_get_serial_sequence('publishers', 'id'))), $2, $3)
+        on conflict(id) do update set name = excluded.name, avatar = excluded.avatar"#,
+    )
+    .bind(id_or_new(publisher.id))
+    .bind(&publisher.name)
+    .bind(&publisher.avatar)
+    .execute(pool)
+    .await
+    .map_err(|e| e.to_string())?;
+    if publisher.id != 0 {
+        sync_sequence(pool, "publishers")
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+async fn upsert_tag(pool: &PgPool, tag: &Tag) -> Result<(), String> {
+    if tag.name.is_empty() {
+        return Err("tag needs a name".to_string());
+    }
+    sqlx::query(
+        r#"insert into tags(id, name, kind) values(coalesce($1, nextval(pg_get_serial_sequence('tags', 'id'))), $2, $3::tag_kind)
+        on conflict(id) do update set name = excluded.name, kind = excluded.kind"#,
+    )
+    .bind(id_or_new(tag.id))
+    .bind(&tag.name)
+    .bind(tag_kind_name(tag.kind()))
+    .execute(pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            format!("Tag {} already exists", tag.name)
+        }
+        e => e.to_string(),
+    })?;
+    if tag.id != 0 {
+        sync_sequence(pool, "tags")
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+async fn upsert_content(pool: &PgPool, content: &Content) -> Result<u32, String> {
+    if content.name.is_empty() {
+        return Err("content needs a name".to_string());
+    }
+    let (publish_at, expire_at) = window(content.publish_at.as_ref(), content.expire_at.as_ref())?;
+    check_details(content.content_type(), content.details.as_ref())?;
+    check_images(content.content_type(), &content.images)?;
+
+    let mut tx = pool.begin().await.map_err(|e| e.to_string())?;
+    let id: i32 = sqlx::query_scalar(
+        r#"insert into contents(id, name, description, url, images, content_type, status, publish_at, expire_at, details)
+        values(coalesce($1, nextval(pg_get_serial_sequence('contents', 'id'))), $2, $3, $4, $5, $6::content_type,
+          $7::content_status, $8, $9, $10)
+        on conflict(id) do update set name = excluded.name, description = excluded.description, url = excluded.url,
+          images = excluded.images, content_type = excluded.content_type, status = excluded.status,
+          publish_at = excluded.publish_at, expire_at = excluded.expire_at, details = excluded.details,
+          updated_at = now()
+        returning id"#,
+    )
+    .bind(id_or_new(content.id))
+    .bind(&content.name)
+    .bind(&content.description)
+    .bind(&content.url)
+    .bind(Json(content.images.iter().map(ImageRow::from).collect::<Vec<_>>()))
+    .bind(content_type_name(content.content_type()))
+    .bind(content_status_name(content.status()))
+    .bind(publish_at)
+    .bind(expire_at)
+    .bind(content.details.as_ref().map(Json))
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| e.to_string())?;
+    let publisher_ids = content.publishers.iter().map(|p| p.id).collect::<Vec<_>>();
+    let tag_ids = content.tags.iter().map(|t| t.id).collect::<Vec<_>>();
+    set_publishers(&mut tx, id, &publisher_ids)
+        .await
+        .map_err(|e| e.message().to_string())?;
+    set_tags(&mut tx, id, &tag_ids)
+        .await
+        .map_err(|e| e.message().to_string())?;
+    tx.commit().await.map_err(|e| e.to_string())?;
+    if content.id != 0 {
+        sync_sequence(pool, "contents")
+            .await
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(id as u32)
+}
+
+fn id_or_new(id: u32) -> Option<i32> {
+    (id != 0).then_some(id as i32)
+}
+
+/// explicit ids bypass the sequence, move it past them so the next new row doesn't take one
+async fn sync_sequence(pool: &PgPool, table: &str) -> Result<(), sqlx::Error> {
+    sqlx::query(&format!(
+        "select setval(pg_get_serial_sequence('{0}', 'id'), greatest((select max(id) from {0}), 1))",
+        table
+    ))
+    .execute(pool)
+    .await?;
+    Ok(())
+}
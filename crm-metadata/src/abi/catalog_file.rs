@@ -0,0 +1,550 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::pb::{
+    catalog_record::Record, content::Details, CatalogRecord, Content, ContentStatus, ContentType,
+    ImportError, Publisher, Tag, TagKind,
+};
+
+use super::{
+    content::{
+        content_status_from_name, content_status_name, content_type_from_name, content_type_name,
+        ts_to_utc, utc_to_ts,
+    },
+    image::ImageRow,
+    tag::{tag_kind_from_name, tag_kind_name},
+};
+
+/// catalog files are picked by extension, JSON Lines or CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Jsonl,
+    Csv,
+}
+
+/// one line of a catalog file, ids are kept so upserts hit the same rows in every environment
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum FileRecord {
+    Publisher(PublisherRecord),
+    Tag(TagRecord),
+    Content(Box<ContentRecord>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct PublisherRecord {
+    #[serde(default)]
+    id: u32,
+    name: String,
+    #[serde(default)]
+    avatar: String,
+}
+
+/// `kind` names the record, so the tag's own kind goes by `tag_kind`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct TagRecord {
+    #[serde(default)]
+    id: u32,
+    name: String,
+    #[serde(default)]
+    tag_kind: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ContentRecord {
+    #[serde(default)]
+    id: u32,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    url: String,
+    content_type: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    publish_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    expire_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    publisher_ids: Vec<u32>,
+    #[serde(default)]
+    tag_ids: Vec<u32>,
+    #[serde(default)]
+    images: Vec<ImageRow>,
+    #[serde(default)]
+    details: Option<Details>,
+}
+
+/// a spreadsheet row, id lists are separated by `;` and images / details are JSON cells
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CsvRow {
+    kind: String,
+    id: Option<u32>,
+    name: String,
+    description: Option<String>,
+    url: Option<String>,
+    avatar: Option<String>,
+    tag_kind: Option<String>,
+    content_type: Option<String>,
+    status: Option<String>,
+    publish_at: Option<DateTime<Utc>>,
+    expire_at: Option<DateTime<Utc>>,
+    publisher_ids: Option<String>,
+    tag_ids: Option<String>,
+    images: Option<String>,
+    details: Option<String>,
+}
+
+impl CatalogFormat {
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("jsonl") | Some("ndjson") => Ok(Self::Jsonl),
+            Some("csv") => Ok(Self::Csv),
+            _ => bail!(
+                "unknown catalog format of {}, expected .jsonl or .csv",
+                path.display()
+            ),
+        }
+    }
+}
+
+/// parse a catalog file, a line that can't be parsed is kept as an error so it ends up in the report
+pub fn read_catalog(path: impl AsRef<Path>) -> Result<Vec<Result<CatalogRecord, ImportError>>> {
+    let path = path.as_ref();
+    let file = File::open(path).with_context(|| format!("open {} failed", path.display()))?;
+    match CatalogFormat::from_path(path)? {
+        CatalogFormat::Jsonl => read_jsonl(BufReader::new(file)),
+        CatalogFormat::Csv => read_csv(file),
+    }
+}
+
+pub fn write_catalog(path: impl AsRef<Path>, records: &[CatalogRecord]) -> Result<()> {
+    let path = path.as_ref();
+    let format = CatalogFormat::from_path(path)?;
+    let file = File::create(path).with_context(|| format!("create {} failed", path.display()))?;
+    let records = records
+        .iter()
+        .filter_map(|r| r.record.clone().map(FileRecord::from));
+    match format {
+        CatalogFormat::Jsonl => {
+            let mut writer = BufWriter::new(file);
+            for record in records {
+                serde_json::to_writer(&mut writer, &record)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+        }
+        CatalogFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(file);
+            for record in records {
+                writer.serialize(CsvRow::try_from(record)?)?;
+            }
+            writer.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn read_jsonl(reader: impl BufRead) -> Result<Vec<Result<CatalogRecord, ImportError>>> {
+    let mut records = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line_no = i as u32 + 1;
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str::<FileRecord>(&line)
+            .map_err(|e| e.to_string())
+            .and_then(Record::try_from);
+        records.push(to_catalog_record(line_no, record));
+    }
+    Ok(records)
+}
+
+fn read_csv(reader: impl std::io::Read) -> Result<Vec<Result<CatalogRecord, ImportError>>> {
+    let mut reader = csv::Reader::from_reader(reader);
+    let headers = reader.headers()?.clone();
+    let mut records = Vec::new();
+    for row in reader.records() {
+        let row = match row {
+            Ok(row) => row,
+            Err(e) if e.is_io_error() => return Err(e.into()),
+            // e.g. a wrong field count, only that record fails
+            Err(e) => {
+                let line = e.position().map(|p| p.line() as u32).unwrap_or_default();
+                let reason = e.to_string();
+                records.push(Err(ImportError { line, reason }));
+                continue;
+            }
+        };
+        let line_no = row.position().map(|p| p.line() as u32).unwrap_or_default();
+        let record = row
+            .deserialize::<CsvRow>(Some(&headers))
+            .map_err(|e| e.to_string())
+            .and_then(FileRecord::try_from)
+            .and_then(Record::try_from);
+        records.push(to_catalog_record(line_no, record));
+    }
+    Ok(records)
+}
+
+fn to_catalog_record(
+    line: u32,
+    record: Result<Record, String>,
+) -> Result<CatalogRecord, ImportError> {
+    record
+        .map(|record| CatalogRecord {
+            line,
+            record: Some(record),
+        })
+        .map_err(|reason| ImportError { line, reason })
+}
+
+impl TryFrom<FileRecord> for Record {
+    type Error = String;
+
+    fn try_from(record: FileRecord) -> Result<Self, Self::Error> {
+        let record = match record {
+            FileRecord::Publisher(p) => Record::Publisher(Publisher {
+                id: p.id,
+                name: p.name,
+                avatar: p.avatar,
+            }),
+            FileRecord::Tag(t) => {
+                let kind = match t.tag_kind.as_deref() {
+                    None | Some("") => TagKind::Tag,
+                    Some(name) => match tag_kind_from_name(name) {
+                        TagKind::Unspecified => return Err(format!("unknown tag kind {}", name)),
+                        kind => kind,
+                    },
+                };
+                Record::Tag(Tag {
+                    id: t.id,
+                    name: t.name,
+                    kind: kind as i32,
+                })
+            }
+            FileRecord::Content(c) => {
+                let content_type = match content_type_from_name(&c.content_type) {
+                    ContentType::Unspecified => {
+                        return Err(format!("unknown content type {}", c.content_type))
+                    }
+                    content_type => content_type,
+                };
+                let status = match c.status.as_deref() {
+                    // like CreateContent, nothing goes live unless the file says so
+                    None | Some("") => ContentStatus::Draft,
+                    Some(name) => match content_status_from_name(name) {
+                        ContentStatus::Unspecified => {
+                            return Err(format!("unknown content status {}", name))
+                        }
+                        status => status,
+                    },
+                };
+                Record::Content(Content {
+                    id: c.id,
+                    name: c.name,
+                    description: c.description,
+                    url: c.url,
+                    content_type: content_type as i32,
+                    status: status as i32,
+                    publish_at: c.publish_at.map(utc_to_ts),
+                    expire_at: c.expire_at.map(utc_to_ts),
+                    publishers: c
+                        .publisher_ids
+                        .into_iter()
+                        .map(|id| Publisher {
+                            id,
+                            ..Default::default()
+                        })
+                        .collect(),
+                    tags: c
+                        .tag_ids
+                        .into_iter()
+                        .map(|id| Tag {
+                            id,
+                            ..Default::default()
+                        })
+                        .collect(),
+                    images: c.images.into_iter().map(Into::into).collect(),
+                    details: c.details,
+                    ..Default::default()
+                })
+            }
+        };
+        Ok(record)
+    }
+}
+
+impl From<Record> for FileRecord {
+    fn from(record: Record) -> Self {
+        match record {
+            Record::Publisher(p) => FileRecord::Publisher(PublisherRecord {
+                id: p.id,
+                name: p.name,
+                avatar: p.avatar,
+            }),
+            Record::Tag(t) => FileRecord::Tag(TagRecord {
+                id: t.id,
+                tag_kind: Some(tag_kind_name(t.kind()).to_string()),
+                name: t.name,
+            }),
+            Record::Content(c) => FileRecord::Content(Box::new(ContentRecord {
+                id: c.id,
+                content_type: content_type_name(c.content_type()).to_string(),
+                status: Some(content_status_name(c.status()).to_string()),
+                publish_at: c.publish_at.as_ref().and_then(ts_to_utc),
+                expire_at: c.expire_at.as_ref().and_then(ts_to_utc),
+                publisher_ids: c.publishers.iter().map(|p| p.id).collect(),
+                tag_ids: c.tags.iter().map(|t| t.id).collect(),
+                images: c.images.iter().map(ImageRow::from).collect(),
+                name: c.name,
+                description: c.description,
+                url: c.url,
+                details: c.details,
+            })),
+        }
+    }
+}
+
+impl TryFrom<CsvRow> for FileRecord {
+    type Error = String;
+
+    fn try_from(row: CsvRow) -> Result<Self, Self::Error> {
+        match row.kind.as_str() {
+            "publisher" => Ok(FileRecord::Publisher(PublisherRecord {
+                id: row.id.unwrap_or_default(),
+                name: row.name,
+                avatar: row.avatar.unwrap_or_default(),
+            })),
+            "tag" => Ok(FileRecord::Tag(TagRecord {
+                id: row.id.unwrap_or_default(),
+                name: row.name,
+                tag_kind: row.tag_kind,
+            })),
+            "content" => Ok(FileRecord::Content(Box::new(ContentRecord {
+                id: row.id.unwrap_or_default(),
+                name: row.name,
+                description: row.description.unwrap_or_default(),
+                url: row.url.unwrap_or_default(),
+                content_type: row.content_type.unwrap_or_default(),
+                status: row.status,
+                publish_at: row.publish_at,
+                expire_at: row.expire_at,
+                publisher_ids: parse_ids(row.publisher_ids.as_deref())?,
+                tag_ids: parse_ids(row.tag_ids.as_deref())?,
+                images: parse_json(row.images.as_deref(), "images")?.unwrap_or_default(),
+                details: parse_json(row.details.as_deref(), "details")?,
+            }))),
+            kind => Err(format!("unknown record kind {}", kind)),
+        }
+    }
+}
+
+impl TryFrom<FileRecord> for CsvRow {
+    type Error = serde_json::Error;
+
+    fn try_from(record: FileRecord) -> Result<Self, Self::Error> {
+        let row = match record {
+            FileRecord::Publisher(p) => CsvRow {
+                kind: "publisher".to_string(),
+                id: Some(p.id),
+                name: p.name,
+                avatar: Some(p.avatar),
+                ..Default::default()
+            },
+            FileRecord::Tag(t) => CsvRow {
+                kind: "tag".to_string(),
+                id: Some(t.id),
+                name: t.name,
+                tag_kind: t.tag_kind,
+                ..Default::default()
+            },
+            FileRecord::Content(c) => CsvRow {
+                kind: "content".to_string(),
+                id: Some(c.id),
+                name: c.name,
+                description: Some(c.description),
+                url: Some(c.url),
+                avatar: None,
+                tag_kind: None,
+                content_type: Some(c.content_type),
+                status: c.status,
+                publish_at: c.publish_at,
+                expire_at: c.expire_at,
+                publisher_ids: Some(join_ids(&c.publisher_ids)),
+                tag_ids: Some(join_ids(&c.tag_ids)),
+                images: Some(serde_json::to_string(&c.images)?),
+                details: c.details.as_ref().map(serde_json::to_string).transpose()?,
+            },
+        };
+        Ok(row)
+    }
+}
+
+fn parse_ids(cell: Option<&str>) -> Result<Vec<u32>, String> {
+    cell.unwrap_or_default()
+        .split(';')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(|id| id.parse().map_err(|_| format!("invalid id {}", id)))
+        .collect()
+}
+
+fn join_ids(ids: &[u32]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(
+    cell: Option<&str>,
+    column: &str,
+) -> Result<Option<T>, String> {
+    match cell.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(cell) => serde_json::from_str(cell)
+            .map(Some)
+            .map_err(|e| format!("invalid {}: {}", column, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{Image, ImageFormat, ImageRole, MovieDetails};
+    use prost_types::Timestamp;
+
+    fn sample() -> Vec<CatalogRecord> {
+        let publisher = Publisher {
+            id: 7,
+            name: "Studio".to_string(),
+            avatar: "https://example.com/avatar.png".to_string(),
+        };
+        let tag = Tag {
+            id: 3,
+            name: "Sci-Fi".to_string(),
+            kind: TagKind::Genre as i32,
+        };
+        let content = Content {
+            id: 42,
+            name: "A movie, with a comma".to_string(),
+            description: "line one\nline two".to_string(),
+            url: "https://example.com/42".to_string(),
+            content_type: ContentType::Movie as i32,
+            status: ContentStatus::Draft as i32,
+            publish_at: Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 0,
+            }),
+            publishers: vec![Publisher {
+                id: 7,
+                ..Default::default()
+            }],
+            tags: vec![
+                Tag {
+                    id: 1,
+                    ..Default::default()
+                },
+                Tag {
+                    id: 3,
+                    ..Default::default()
+                },
+            ],
+            images: vec![Image {
+                role: ImageRole::Poster as i32,
+                url: "https://example.com/poster.jpg".to_string(),
+                width: 800,
+                height: 1200,
+                format: ImageFormat::Jpeg as i32,
+            }],
+            details: Some(Details::Movie(MovieDetails {
+                rating: "PG-13".to_string(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        vec![
+            CatalogRecord {
+                line: 1,
+                record: Some(Record::Publisher(publisher)),
+            },
+            CatalogRecord {
+                line: 2,
+                record: Some(Record::Tag(tag)),
+            },
+            CatalogRecord {
+                line: 3,
+                record: Some(Record::Content(content)),
+            },
+        ]
+    }
+
+    fn round_trip(ext: &str) -> Vec<Result<CatalogRecord, ImportError>> {
+        let path = std::env::temp_dir().join(format!("catalog-{}.{}", nanoid::nanoid!(8), ext));
+        write_catalog(&path, &sample()).unwrap();
+        let records = read_catalog(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        records
+    }
+
+    #[test]
+    fn catalog_file_should_round_trip() {
+        let jsonl = round_trip("jsonl");
+        assert_eq!(jsonl, sample().into_iter().map(Ok).collect::<Vec<_>>());
+
+        // quoted cells span lines, records keep the line they start on
+        let csv = round_trip("csv");
+        let records = csv.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+        assert_eq!(records[0].line, 2);
+        assert_eq!(records[1].line, 3);
+        assert_eq!(records[2].line, 4);
+        let records = records.into_iter().map(|r| r.record).collect::<Vec<_>>();
+        let expected = sample().into_iter().map(|r| r.record).collect::<Vec<_>>();
+        assert_eq!(records, expected);
+    }
+
+    #[test]
+    fn missing_status_should_be_draft() {
+        let data = "kind,id,name,content_type,status\ncontent,1,x,short,\n";
+        let records = read_csv(data.as_bytes()).unwrap();
+        let Some(Record::Content(content)) = records[0].as_ref().unwrap().record.as_ref() else {
+            panic!("a content record");
+        };
+        assert_eq!(content.status(), ContentStatus::Draft);
+    }
+
+    #[test]
+    fn bad_lines_should_be_reported() {
+        let data = "{\"kind\":\"publisher\",\"name\":\"ok\"}\n\nnot json\n{\"kind\":\"content\",\"name\":\"x\",\"content_type\":\"opera\"}\n";
+        let records = read_jsonl(data.as_bytes()).unwrap();
+        assert_eq!(records.len(), 3);
+        assert!(records[0].is_ok());
+        assert_eq!(records[1].as_ref().unwrap_err().line, 3);
+        let err = records[2].as_ref().unwrap_err();
+        assert_eq!(err.line, 4);
+        assert_eq!(err.reason, "unknown content type opera");
+
+        let data = "kind,id,name,tag_kind,tag_ids\ncontent,1,x,,1;two\ntag,2,y,mood,\ntag,3,z\ntag,4,w,genre,\n";
+        let records = read_csv(data.as_bytes()).unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0].as_ref().unwrap_err().reason, "invalid id two");
+        assert_eq!(
+            records[1].as_ref().unwrap_err().reason,
+            "unknown tag kind mood"
+        );
+        // a short row fails on its own, the next one still comes through
+        assert_eq!(records[2].as_ref().unwrap_err().line, 4);
+        assert_eq!(records[3].as_ref().unwrap().line, 5);
+    }
+}
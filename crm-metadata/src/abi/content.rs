@@ -0,0 +1,264 @@
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+use sqlx::{types::Json, PgPool, Postgres, Transaction};
+use tonic::{Response, Status};
+
+use super::{
+    details::check_details,
+    image::{check_images, ImageRow},
+    lifecycle::window,
+    publisher::PublisherRow,
+    tag::{set_tags, TagRow},
+};
+
+use crate::{
+    pb::{
+        content::Details, Content, ContentStatus, ContentType, CreateContentRequest,
+        DeleteContentRequest, DeleteContentResponse, GetContentRequest, UpdateContentRequest,
+    },
+    MetaDataService, ServiceResult,
+};
+
+pub(crate) const CONTENT_SELECT: &str = r#"select c.id, c.name, c.description, c.url, c.images, c.content_type::text as content_type,
+  c.created_at, c.views, c.likes, c.dislikes, c.rating, c.trending,
+  c.status::text as status, c.publish_at, c.expire_at, c.details,
+  coalesce(json_agg(json_build_object('id', p.id, 'name', p.name, 'avatar', p.avatar) order by p.id)
+    filter (where p.id is not null), '[]') as publishers,
+  (select coalesce(json_agg(json_build_object('id', t.id, 'name', t.name, 'kind', t.kind) order by t.id), '[]')
+    from content_tags ct join tags t on t.id = ct.tag_id where ct.content_id = c.id) as tags
+from contents c
+  left join content_publishers cp on cp.content_id = c.id
+  left join publishers p on p.id = cp.publisher_id"#;
+
+#[derive(Debug, sqlx::FromRow)]
+pub(crate) struct ContentRow {
+    id: i32,
+    name: String,
+    description: String,
+    url: String,
+    images: Json<Vec<ImageRow>>,
+    content_type: String,
+    created_at: DateTime<Utc>,
+    views: i64,
+    likes: i64,
+    dislikes: i64,
+    rating: f64,
+    trending: f64,
+    status: String,
+    publish_at: Option<DateTime<Utc>>,
+    expire_at: Option<DateTime<Utc>>,
+    details: Option<Json<Details>>,
+    publishers: Json<Vec<PublisherRow>>,
+    tags: Json<Vec<TagRow>>,
+}
+
+impl MetaDataService {
+    pub async fn create_content(&self, req: CreateContentRequest) -> ServiceResult<Content> {
+        let (publish_at, expire_at) = window(req.publish_at.as_ref(), req.expire_at.as_ref())
+            .map_err(Status::invalid_argument)?;
+        let details = req.details.clone().map(Details::from);
+        check_details(req.content_type(), details.as_ref()).map_err(Status::invalid_argument)?;
+        check_images(req.content_type(), &req.images).map_err(Status::invalid_argument)?;
+        let mut tx = self.pool.begin().await.map_err(internal)?;
+        let id: i32 = sqlx::query_scalar(
+            r#"insert into contents(name, description, url, images, content_type, status, publish_at, expire_at, details)
+            values($1, $2, $3, $4, $5::content_type, $6::content_status, $7, $8, $9) returning id"#,
+        )
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(&req.url)
+        .bind(Json(req.images.iter().map(ImageRow::from).collect::<Vec<_>>()))
+        .bind(content_type_name(req.content_type()))
+        .bind(content_status_name(req.status()))
+        .bind(publish_at)
+        .bind(expire_at)
+        .bind(details.map(Json))
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(internal)?;
+        set_publishers(&mut tx, id, &req.publisher_ids).await?;
+        set_tags(&mut tx, id, &req.tag_ids).await?;
+        tx.commit().await.map_err(internal)?;
+        self.get_content(GetContentRequest { id: id as _ }).await
+    }
+
+    pub async fn update_content(&self, req: UpdateContentRequest) -> ServiceResult<Content> {
+        let details = req.details.clone().map(Details::from);
+        check_details(req.content_type(), details.as_ref()).map_err(Status::invalid_argument)?;
+        check_images(req.content_type(), &req.images).map_err(Status::invalid_argument)?;
+        let mut tx = self.pool.begin().await.map_err(internal)?;
+        let ret = sqlx::query(
+            r#"update contents set name = $2, description = $3, url = $4, images = $5,
+            content_type = $6::content_type, details = $7, updated_at = now() where id = $1"#,
+        )
+        .bind(req.id as i32)
+        .bind(&req.name)
+        .bind(&req.description)
+        .bind(&req.url)
+        .bind(Json(
+            req.images.iter().map(ImageRow::from).collect::<Vec<_>>(),
+        ))
+        .bind(content_type_name(req.content_type()))
+        .bind(details.map(Json))
+        .execute(&mut *tx)
+        .await
+        .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("Content {} not found", req.id)));
+        }
+        set_publishers(&mut tx, req.id as _, &req.publisher_ids).await?;
+        set_tags(&mut tx, req.id as _, &req.tag_ids).await?;
+        tx.commit().await.map_err(internal)?;
+        self.cache.invalidate(req.id);
+        self.get_content(GetContentRequest { id: req.id }).await
+    }
+
+    pub async fn delete_content(
+        &self,
+        req: DeleteContentRequest,
+    ) -> ServiceResult<DeleteContentResponse> {
+        let ret = sqlx::query("delete from contents where id = $1")
+            .bind(req.id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("Content {} not found", req.id)));
+        }
+        self.cache.invalidate(req.id);
+        Ok(Response::new(DeleteContentResponse { id: req.id }))
+    }
+
+    pub async fn get_content(&self, req: GetContentRequest) -> ServiceResult<Content> {
+        let content = fetch_contents(&self.pool, &[req.id])
+            .await
+            .map_err(internal)?
+            .pop()
+            .ok_or_else(|| Status::not_found(format!("Content {} not found", req.id)))?;
+        Ok(Response::new(content))
+    }
+}
+
+/// load the given contents with their publishers, unknown ids are left out
+pub(crate) async fn fetch_contents(
+    pool: &PgPool,
+    ids: &[u32],
+) -> Result<Vec<Content>, sqlx::Error> {
+    let ids = ids.iter().map(|id| *id as i32).collect::<Vec<_>>();
+    let rows = sqlx::query_as::<_, ContentRow>(&format!(
+        "{} where c.id = any($1) group by c.id",
+        CONTENT_SELECT
+    ))
+    .bind(ids)
+    .fetch_all(pool)
+    .await?;
+    Ok(rows.into_iter().map(Into::into).collect())
+}
+
+pub(crate) async fn set_publishers(
+    tx: &mut Transaction<'_, Postgres>,
+    content_id: i32,
+    publisher_ids: &[u32],
+) -> Result<(), Status> {
+    sqlx::query("delete from content_publishers where content_id = $1")
+        .bind(content_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(internal)?;
+    let publisher_ids = publisher_ids
+        .iter()
+        .map(|id| *id as i32)
+        .collect::<Vec<_>>();
+    sqlx::query(
+        "insert into content_publishers(content_id, publisher_id) select $1, unnest($2::int[]) on conflict do nothing",
+    )
+    .bind(content_id)
+    .bind(publisher_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            Status::invalid_argument(format!("Unknown publisher: {}", e))
+        }
+        e => internal(e),
+    })?;
+    Ok(())
+}
+
+impl From<ContentRow> for Content {
+    fn from(row: ContentRow) -> Self {
+        Content {
+            id: row.id as _,
+            name: row.name,
+            description: row.description,
+            publishers: row.publishers.0.into_iter().map(Into::into).collect(),
+            url: row.url,
+            images: row.images.0.into_iter().map(Into::into).collect(),
+            content_type: content_type_from_name(&row.content_type) as i32,
+            created_at: Some(utc_to_ts(row.created_at)),
+            views: row.views as _,
+            likes: row.likes as _,
+            dislikes: row.dislikes as _,
+            rating: row.rating,
+            trending: row.trending,
+            status: content_status_from_name(&row.status) as i32,
+            publish_at: row.publish_at.map(utc_to_ts),
+            expire_at: row.expire_at.map(utc_to_ts),
+            details: row.details.map(|d| d.0),
+            tags: row.tags.0.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+pub(crate) fn content_type_name(content_type: ContentType) -> &'static str {
+    match content_type {
+        ContentType::Unspecified => "unspecified",
+        ContentType::Short => "short",
+        ContentType::Vlog => "vlog",
+        ContentType::Movie => "movie",
+        ContentType::AiGenerated => "ai_generated",
+    }
+}
+
+pub(crate) fn content_status_name(status: ContentStatus) -> &'static str {
+    match status {
+        // like the column default, content goes live only when asked to
+        ContentStatus::Unspecified | ContentStatus::Draft => "draft",
+        ContentStatus::Published => "published",
+        ContentStatus::Archived => "archived",
+    }
+}
+
+pub(crate) fn content_status_from_name(name: &str) -> ContentStatus {
+    match name {
+        "draft" => ContentStatus::Draft,
+        "published" => ContentStatus::Published,
+        "archived" => ContentStatus::Archived,
+        _ => ContentStatus::Unspecified,
+    }
+}
+
+pub(crate) fn content_type_from_name(name: &str) -> ContentType {
+    match name {
+        "short" => ContentType::Short,
+        "vlog" => ContentType::Vlog,
+        "movie" => ContentType::Movie,
+        "ai_generated" => ContentType::AiGenerated,
+        _ => ContentType::Unspecified,
+    }
+}
+
+pub(crate) fn utc_to_ts(t: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as _,
+    }
+}
+
+pub(crate) fn ts_to_utc(ts: &Timestamp) -> Option<DateTime<Utc>> {
+    DateTime::from_timestamp(ts.seconds, ts.nanos as _)
+}
+
+pub(crate) fn internal(e: sqlx::Error) -> Status {
+    Status::internal(format!("Failed to access catalog: {}", e))
+}
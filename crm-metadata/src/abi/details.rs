@@ -0,0 +1,74 @@
+use crate::pb::{content::Details, create_content_request, update_content_request, ContentType};
+
+impl From<create_content_request::Details> for Details {
+    fn from(details: create_content_request::Details) -> Self {
+        match details {
+            create_content_request::Details::Vlog(v) => Details::Vlog(v),
+            create_content_request::Details::Movie(v) => Details::Movie(v),
+            create_content_request::Details::AiGenerated(v) => Details::AiGenerated(v),
+        }
+    }
+}
+
+impl From<update_content_request::Details> for Details {
+    fn from(details: update_content_request::Details) -> Self {
+        match details {
+            update_content_request::Details::Vlog(v) => Details::Vlog(v),
+            update_content_request::Details::Movie(v) => Details::Movie(v),
+            update_content_request::Details::AiGenerated(v) => Details::AiGenerated(v),
+        }
+    }
+}
+
+/// details are optional, but when given they must be the ones of the content type
+pub(crate) fn check_details(
+    content_type: ContentType,
+    details: Option<&Details>,
+) -> Result<(), String> {
+    let expected = match details {
+        None => return Ok(()),
+        Some(Details::Vlog(_)) => ContentType::Vlog,
+        Some(Details::Movie(_)) => ContentType::Movie,
+        Some(Details::AiGenerated(_)) => ContentType::AiGenerated,
+    };
+    if expected != content_type {
+        return Err(format!(
+            "{} details don't match content type {}",
+            expected.as_str_name(),
+            content_type.as_str_name()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::MovieDetails;
+
+    #[test]
+    fn check_details_should_work() {
+        let movie = Details::Movie(MovieDetails {
+            runtime_minutes: 128,
+            ..Default::default()
+        });
+        assert!(check_details(ContentType::Movie, Some(&movie)).is_ok());
+        assert!(check_details(ContentType::Short, None).is_ok());
+        assert!(check_details(ContentType::Vlog, Some(&movie)).is_err());
+    }
+
+    #[test]
+    fn details_should_be_tagged_with_kind() {
+        let movie = Details::Movie(MovieDetails {
+            runtime_minutes: 128,
+            rating: "PG-13".to_string(),
+            cast: vec!["Tyr".to_string()],
+        });
+        let json = serde_json::to_value(&movie).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"kind": "movie", "runtime_minutes": 128, "rating": "PG-13", "cast": ["Tyr"]})
+        );
+        assert_eq!(serde_json::from_value::<Details>(json).unwrap(), movie);
+    }
+}
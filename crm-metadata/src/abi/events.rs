@@ -0,0 +1,153 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{NaiveDate, Utc};
+use futures::{Stream, StreamExt};
+use sqlx::PgPool;
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{ContentEvent, ContentEventType, RecordContentEventResponse},
+    trending, MetaDataService, ServiceResult,
+};
+
+use super::content::{internal, ts_to_utc};
+
+/// events folded into one write, a hot content gets one row update per batch instead of per event
+const BATCH_SIZE: usize = 1000;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Counters {
+    views: i64,
+    likes: i64,
+    dislikes: i64,
+}
+
+impl MetaDataService {
+    /// a broken request stream drops the events not flushed yet
+    pub async fn record_content_event(
+        &self,
+        mut stream: impl Stream<Item = Result<ContentEvent, Status>> + Unpin,
+    ) -> ServiceResult<RecordContentEventResponse> {
+        let mut batch: HashMap<(i32, NaiveDate), Counters> = HashMap::new();
+        let (mut accepted, mut rejected, mut pending) = (0, 0, 0u64);
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            let day = event
+                .occurred_at
+                .as_ref()
+                .and_then(ts_to_utc)
+                .unwrap_or_else(Utc::now)
+                .date_naive();
+            let counters = batch.entry((event.content_id as i32, day)).or_default();
+            match event.event_type() {
+                ContentEventType::View => counters.views += 1,
+                ContentEventType::Like => counters.likes += 1,
+                ContentEventType::Dislike => counters.dislikes += 1,
+                ContentEventType::Unspecified => {
+                    rejected += 1;
+                    continue;
+                }
+            }
+            pending += 1;
+            if pending >= BATCH_SIZE as u64 {
+                let n = flush(&self.pool, std::mem::take(&mut batch)).await?;
+                accepted += n;
+                rejected += pending - n;
+                pending = 0;
+            }
+        }
+        let n = flush(&self.pool, batch).await?;
+        accepted += n;
+        rejected += pending - n;
+        Ok(Response::new(RecordContentEventResponse {
+            accepted,
+            rejected,
+        }))
+    }
+}
+
+/// add the counters to the contents and their daily rollups and refresh trending, returns the events of known contents
+async fn flush(pool: &PgPool, batch: HashMap<(i32, NaiveDate), Counters>) -> Result<u64, Status> {
+    if batch.is_empty() {
+        return Ok(0);
+    }
+    let mut totals: BTreeMap<i32, Counters> = BTreeMap::new();
+    for ((id, _), c) in &batch {
+        let total = totals.entry(*id).or_default();
+        total.views += c.views;
+        total.likes += c.likes;
+        total.dislikes += c.dislikes;
+    }
+
+    let mut tx = pool.begin().await.map_err(internal)?;
+    // lock in id order so concurrent batches can't deadlock, unknown ids simply don't come back
+    let ids = totals.keys().copied().collect::<Vec<_>>();
+    let known: Vec<i32> =
+        sqlx::query_scalar("select id from contents where id = any($1) order by id for update")
+            .bind(&ids)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(internal)?;
+    if known.is_empty() {
+        return Ok(0);
+    }
+
+    let (mut views, mut likes, mut dislikes) = (vec![], vec![], vec![]);
+    for id in &known {
+        let c = totals[id];
+        views.push(c.views);
+        likes.push(c.likes);
+        dislikes.push(c.dislikes);
+    }
+    sqlx::query(
+        r#"update contents c set views = c.views + d.views, likes = c.likes + d.likes, dislikes = c.dislikes + d.dislikes
+        from unnest($1::int[], $2::bigint[], $3::bigint[], $4::bigint[]) as d(id, views, likes, dislikes)
+        where c.id = d.id"#,
+    )
+    .bind(&known)
+    .bind(&views)
+    .bind(&likes)
+    .bind(&dislikes)
+    .execute(&mut *tx)
+    .await
+    .map_err(internal)?;
+
+    let (mut ids, mut days, mut views, mut likes, mut dislikes) =
+        (vec![], vec![], vec![], vec![], vec![]);
+    for ((id, day), c) in batch {
+        if known.binary_search(&id).is_ok() {
+            ids.push(id);
+            days.push(day);
+            views.push(c.views);
+            likes.push(c.likes);
+            dislikes.push(c.dislikes);
+        }
+    }
+    sqlx::query(
+        r#"insert into content_daily_stats(content_id, day, views, likes, dislikes)
+        select * from unnest($1::int[], $2::date[], $3::bigint[], $4::bigint[], $5::bigint[])
+        on conflict(content_id, day) do update set views = content_daily_stats.views + excluded.views,
+          likes = content_daily_stats.likes + excluded.likes, dislikes = content_daily_stats.dislikes + excluded.dislikes"#,
+    )
+    .bind(&ids)
+    .bind(&days)
+    .bind(&views)
+    .bind(&likes)
+    .bind(&dislikes)
+    .execute(&mut *tx)
+    .await
+    .map_err(internal)?;
+    sqlx::query(
+        "update contents set trending = content_trending(id), trending_day = (now() at time zone 'utc')::date where id = any($1)",
+    )
+    .bind(&known)
+    .execute(&mut *tx)
+    .await
+    .map_err(internal)?;
+    tx.commit().await.map_err(internal)?;
+
+    // contents without new events still decay, TrendingJob covers the days without events at all
+    trending::refresh_stale(pool).await.map_err(internal)?;
+
+    Ok(views.iter().chain(&likes).chain(&dislikes).sum::<i64>() as u64)
+}
@@ -0,0 +1,188 @@
+use minijinja::{value::ViaDeserialize, Error, ErrorKind};
+use serde::{Deserialize, Serialize};
+
+use crate::pb::{ContentType, Image, ImageFormat, ImageRole};
+
+/// wider images get scaled down by mail clients anyway, 2x of a 600px wide layout
+const EMAIL_MAX_WIDTH: u32 = 1200;
+
+/// an image variant as stored in the catalog and seen by templates, role and format by name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ImageRow {
+    role: String,
+    url: String,
+    width: u32,
+    height: u32,
+    format: String,
+}
+
+/// roles a content of the type can't be created without
+pub(crate) fn required_roles(content_type: ContentType) -> &'static [ImageRole] {
+    match content_type {
+        ContentType::Unspecified => &[],
+        ContentType::Movie => &[ImageRole::Poster, ImageRole::Thumbnail],
+        ContentType::Short | ContentType::Vlog | ContentType::AiGenerated => {
+            &[ImageRole::Thumbnail]
+        }
+    }
+}
+
+pub(crate) fn check_images(content_type: ContentType, images: &[Image]) -> Result<(), String> {
+    if let Some(image) = images
+        .iter()
+        .find(|i| i.url.is_empty() || i.role() == ImageRole::Unspecified)
+    {
+        return Err(format!("Image needs a role and an url: {:?}", image));
+    }
+    for role in required_roles(content_type) {
+        if !images.iter().any(|i| i.role() == *role) {
+            return Err(format!(
+                "{} content needs a {} image",
+                content_type.as_str_name(),
+                role.as_str_name()
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// `{{ content.images | image("thumbnail", "email") }}` is the url of the variant of the role that
+/// fits the target best, or empty when there is none. emails can't rely on webp or avif support and
+/// take the widest one up to 1200px, in app the widest one is used
+pub(crate) fn image_filter(
+    images: ViaDeserialize<Vec<ImageRow>>,
+    role: &str,
+    target: Option<&str>,
+) -> Result<String, Error> {
+    let candidates = images.iter().filter(|i| i.role == role);
+    let picked = match target.unwrap_or("app") {
+        "app" => candidates.max_by_key(|i| i.width),
+        "email" => {
+            let candidates = candidates
+                .filter(|i| i.format != "webp" && i.format != "avif")
+                .collect::<Vec<_>>();
+            candidates
+                .iter()
+                .filter(|i| i.width <= EMAIL_MAX_WIDTH)
+                .max_by_key(|i| i.width)
+                .or_else(|| candidates.iter().min_by_key(|i| i.width))
+                .copied()
+        }
+        target => {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!("unknown image target {}, use app or email", target),
+            ))
+        }
+    };
+    Ok(picked.map(|i| i.url.clone()).unwrap_or_default())
+}
+
+impl From<&Image> for ImageRow {
+    fn from(image: &Image) -> Self {
+        ImageRow {
+            role: image_role_name(image.role()).to_string(),
+            url: image.url.clone(),
+            width: image.width,
+            height: image.height,
+            format: image_format_name(image.format()).to_string(),
+        }
+    }
+}
+
+impl From<ImageRow> for Image {
+    fn from(row: ImageRow) -> Self {
+        let role = match row.role.as_str() {
+            "poster" => ImageRole::Poster,
+            "thumbnail" => ImageRole::Thumbnail,
+            "banner" => ImageRole::Banner,
+            _ => ImageRole::Unspecified,
+        };
+        let format = match row.format.as_str() {
+            "jpeg" => ImageFormat::Jpeg,
+            "png" => ImageFormat::Png,
+            "webp" => ImageFormat::Webp,
+            "avif" => ImageFormat::Avif,
+            _ => ImageFormat::Unspecified,
+        };
+        Image {
+            role: role as i32,
+            url: row.url,
+            width: row.width,
+            height: row.height,
+            format: format as i32,
+        }
+    }
+}
+
+fn image_role_name(role: ImageRole) -> &'static str {
+    match role {
+        ImageRole::Unspecified => "unspecified",
+        ImageRole::Poster => "poster",
+        ImageRole::Thumbnail => "thumbnail",
+        ImageRole::Banner => "banner",
+    }
+}
+
+fn image_format_name(format: ImageFormat) -> &'static str {
+    match format {
+        ImageFormat::Unspecified => "unspecified",
+        ImageFormat::Jpeg => "jpeg",
+        ImageFormat::Png => "png",
+        ImageFormat::Webp => "webp",
+        ImageFormat::Avif => "avif",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(role: ImageRole, width: u32, format: ImageFormat) -> Image {
+        Image {
+            role: role as i32,
+            url: format!("https://example.com/{}.{}", width, format.as_str_name()),
+            width,
+            height: width,
+            format: format as i32,
+        }
+    }
+
+    #[test]
+    fn check_images_should_work() {
+        let poster = image(ImageRole::Poster, 800, ImageFormat::Jpeg);
+        let thumbnail = image(ImageRole::Thumbnail, 200, ImageFormat::Jpeg);
+        assert!(check_images(ContentType::Unspecified, &[]).is_ok());
+        assert!(check_images(ContentType::Short, std::slice::from_ref(&thumbnail)).is_ok());
+        assert!(check_images(ContentType::Movie, std::slice::from_ref(&thumbnail)).is_err());
+        assert!(check_images(ContentType::Movie, &[poster, thumbnail]).is_ok());
+        let no_url = Image {
+            url: String::new(),
+            ..image(ImageRole::Thumbnail, 200, ImageFormat::Jpeg)
+        };
+        assert!(check_images(ContentType::Short, &[no_url]).is_err());
+    }
+
+    #[test]
+    fn image_filter_should_pick_by_target() {
+        let images = [
+            image(ImageRole::Banner, 2400, ImageFormat::Jpeg),
+            image(ImageRole::Banner, 1200, ImageFormat::Webp),
+            image(ImageRole::Banner, 600, ImageFormat::Png),
+        ]
+        .iter()
+        .map(ImageRow::from)
+        .collect::<Vec<_>>();
+        let pick = |role, target| image_filter(ViaDeserialize(images.clone()), role, target);
+        assert_eq!(
+            pick("banner", None).unwrap(),
+            "https://example.com/2400.IMAGE_FORMAT_JPEG"
+        );
+        assert_eq!(
+            pick("banner", Some("email")).unwrap(),
+            "https://example.com/600.IMAGE_FORMAT_PNG"
+        );
+        assert_eq!(pick("poster", Some("email")).unwrap(), "");
+        assert!(pick("banner", Some("tv")).is_err());
+    }
+}
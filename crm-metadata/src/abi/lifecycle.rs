@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+use tonic::Status;
+
+use crate::{
+    pb::{ArchiveContentRequest, Content, ContentStatus, GetContentRequest, PublishContentRequest},
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{internal, ts_to_utc};
+
+impl MetaDataService {
+    pub async fn publish_content(&self, req: PublishContentRequest) -> ServiceResult<Content> {
+        let (publish_at, expire_at) = window(req.publish_at.as_ref(), req.expire_at.as_ref())
+            .map_err(Status::invalid_argument)?;
+        let ret = sqlx::query(
+            r#"update contents set status = 'published', publish_at = $2, expire_at = $3, updated_at = now()
+            where id = $1 and status in ('draft', 'published')"#,
+        )
+        .bind(req.id as i32)
+        .bind(publish_at)
+        .bind(expire_at)
+        .execute(&self.pool)
+        .await
+        .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(self.transition_error(req.id, "published").await);
+        }
+        self.cache.invalidate(req.id);
+        self.get_content(GetContentRequest { id: req.id }).await
+    }
+
+    pub async fn archive_content(&self, req: ArchiveContentRequest) -> ServiceResult<Content> {
+        let ret = sqlx::query(
+            r#"update contents set status = 'archived', updated_at = now()
+            where id = $1 and status in ('draft', 'published')"#,
+        )
+        .bind(req.id as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(self.transition_error(req.id, "archived").await);
+        }
+        self.cache.invalidate(req.id);
+        self.get_content(GetContentRequest { id: req.id }).await
+    }
+
+    /// the content is either unknown or in a status the transition doesn't start from
+    async fn transition_error(&self, id: u32, to: &str) -> Status {
+        let status: Option<String> =
+            match sqlx::query_scalar("select status::text from contents where id = $1")
+                .bind(id as i32)
+                .fetch_optional(&self.pool)
+                .await
+            {
+                Ok(status) => status,
+                Err(e) => return internal(e),
+            };
+        match status {
+            Some(status) => Status::failed_precondition(format!(
+                "Content {} is {} and can't be {}",
+                id, status, to
+            )),
+            None => Status::not_found(format!("Content {} not found", id)),
+        }
+    }
+}
+
+type Window = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// the availability window, both ends are optional but it can't be empty
+pub(crate) fn window(
+    publish_at: Option<&Timestamp>,
+    expire_at: Option<&Timestamp>,
+) -> Result<Window, String> {
+    let publish_at = publish_at.and_then(ts_to_utc);
+    let expire_at = expire_at.and_then(ts_to_utc);
+    if let (Some(publish_at), Some(expire_at)) = (publish_at, expire_at) {
+        if expire_at <= publish_at {
+            return Err("expire_at must be after publish_at".to_string());
+        }
+    }
+    Ok((publish_at, expire_at))
+}
+
+/// why the content can't be promoted right now, mirrors content_available() in the database
+pub(crate) fn unavailable_reason(content: &Content, now: DateTime<Utc>) -> Option<String> {
+    match content.status() {
+        ContentStatus::Published => {}
+        ContentStatus::Draft => return Some("content is a draft".to_string()),
+        ContentStatus::Archived => return Some("content is archived".to_string()),
+        ContentStatus::Unspecified => return Some("content has no status".to_string()),
+    }
+    if let Some(publish_at) = content.publish_at.as_ref().and_then(ts_to_utc) {
+        if publish_at > now {
+            return Some(format!("content is published at {}", publish_at));
+        }
+    }
+    if let Some(expire_at) = content.expire_at.as_ref().and_then(ts_to_utc) {
+        if expire_at <= now {
+            return Some(format!("content expired at {}", expire_at));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::content::utc_to_ts;
+    use chrono::Duration;
+
+    #[test]
+    fn unavailable_reason_should_work() {
+        let now = Utc::now();
+        let mut content = Content {
+            status: ContentStatus::Published as i32,
+            ..Default::default()
+        };
+        assert_eq!(unavailable_reason(&content, now), None);
+        content.publish_at = Some(utc_to_ts(now + Duration::hours(1)));
+        assert!(unavailable_reason(&content, now).is_some());
+        content.publish_at = Some(utc_to_ts(now - Duration::hours(1)));
+        content.expire_at = Some(utc_to_ts(now));
+        assert!(unavailable_reason(&content, now).is_some());
+        content.expire_at = None;
+        content.status = ContentStatus::Draft as i32;
+        assert_eq!(
+            unavailable_reason(&content, now).as_deref(),
+            Some("content is a draft")
+        );
+    }
+}
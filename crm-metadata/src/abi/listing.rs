@@ -0,0 +1,230 @@
+use chrono::DateTime;
+use sqlx::{Postgres, QueryBuilder};
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{Content, ContentSort, ListContentsRequest, ListContentsResponse},
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{content_type_name, internal, ts_to_utc, ContentRow, CONTENT_SELECT};
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+const MAX_PAGE_SIZE: usize = 100;
+
+/// position after the last content of a page, encoded as `<sort>:<key>:<id>`
+#[derive(Debug, PartialEq)]
+struct Cursor {
+    key: CursorKey,
+    id: i32,
+}
+
+/// scores are floats, the other sorts are integers (timestamps in micros)
+#[derive(Debug, PartialEq)]
+enum CursorKey {
+    Int(i64),
+    Float(f64),
+}
+
+impl MetaDataService {
+    pub async fn list_contents(
+        &self,
+        req: ListContentsRequest,
+    ) -> ServiceResult<ListContentsResponse> {
+        let sort = req.sort();
+        let page_size = match req.page_size as usize {
+            0 => DEFAULT_PAGE_SIZE,
+            n => n.min(MAX_PAGE_SIZE),
+        };
+        let cursor = if req.cursor.is_empty() {
+            None
+        } else {
+            Some(Cursor::decode(sort, &req.cursor).ok_or_else(|| {
+                Status::invalid_argument(format!("Invalid cursor: {}", req.cursor))
+            })?)
+        };
+
+        let mut builder = QueryBuilder::<Postgres>::new(CONTENT_SELECT);
+        builder.push(" where true");
+        if !req.include_unavailable {
+            builder.push(" and content_available(c)");
+        }
+        if req.content_type.is_some() {
+            builder
+                .push(" and c.content_type = ")
+                .push_bind(content_type_name(req.content_type()))
+                .push("::content_type");
+        }
+        if let Some(after) = req.created_after.as_ref().and_then(ts_to_utc) {
+            builder.push(" and c.created_at >= ").push_bind(after);
+        }
+        if let Some(before) = req.created_before.as_ref().and_then(ts_to_utc) {
+            builder.push(" and c.created_at < ").push_bind(before);
+        }
+        if let Some(publisher_id) = req.publisher_id {
+            // filter in a subquery so the content still lists all of its publishers
+            builder
+                .push(" and exists (select 1 from content_publishers x where x.content_id = c.id and x.publisher_id = ")
+                .push_bind(publisher_id as i32)
+                .push(")");
+        }
+        if !req.tag_ids.is_empty() {
+            let mut tag_ids = req.tag_ids.iter().map(|id| *id as i32).collect::<Vec<_>>();
+            tag_ids.sort_unstable();
+            tag_ids.dedup();
+            builder
+                .push(" and (select count(*) from content_tags x where x.content_id = c.id and x.tag_id = any(")
+                .push_bind(tag_ids.clone())
+                .push(")) = ")
+                .push_bind(tag_ids.len() as i64);
+        }
+        if !req.name.is_empty() {
+            builder
+                .push(" and c.name ilike ")
+                .push_bind(format!("%{}%", escape_like(&req.name)));
+        }
+        let column = sort_column(sort);
+        if let Some(cursor) = cursor {
+            builder.push(format!(" and ({}, c.id) < (", column));
+            match (sort, cursor.key) {
+                (ContentSort::Recent, CursorKey::Int(key)) => builder.push_bind(
+                    DateTime::from_timestamp_micros(key)
+                        .ok_or_else(|| Status::invalid_argument("Invalid cursor"))?,
+                ),
+                (_, CursorKey::Int(key)) => builder.push_bind(key),
+                (_, CursorKey::Float(key)) => builder.push_bind(key),
+            };
+            builder.push(", ").push_bind(cursor.id).push(")");
+        }
+        builder
+            .push(format!(
+                " group by c.id order by {} desc, c.id desc limit ",
+                column
+            ))
+            .push_bind(page_size as i64 + 1);
+
+        let mut contents: Vec<Content> = builder
+            .build_query_as::<ContentRow>()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(internal)?
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        // one extra row was fetched to know whether there is a next page
+        let next_cursor = if contents.len() > page_size {
+            contents.truncate(page_size);
+            contents
+                .last()
+                .map(|c| Cursor::of(sort, c).encode(sort))
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+        Ok(Response::new(ListContentsResponse {
+            contents,
+            next_cursor,
+        }))
+    }
+}
+
+impl Cursor {
+    fn of(sort: ContentSort, content: &Content) -> Self {
+        let key = match sort {
+            ContentSort::Recent => CursorKey::Int(
+                content
+                    .created_at
+                    .as_ref()
+                    .and_then(ts_to_utc)
+                    .map(|t| t.timestamp_micros())
+                    .unwrap_or_default(),
+            ),
+            ContentSort::Views => CursorKey::Int(content.views as i64),
+            ContentSort::Likes => CursorKey::Int(content.likes as i64),
+            ContentSort::Rating => CursorKey::Float(content.rating),
+            ContentSort::Trending => CursorKey::Float(content.trending),
+        };
+        Cursor {
+            key,
+            id: content.id as _,
+        }
+    }
+
+    fn encode(&self, sort: ContentSort) -> String {
+        // floats are printed in their shortest form that parses back to the same value
+        let key = match self.key {
+            CursorKey::Int(key) => key.to_string(),
+            CursorKey::Float(key) => key.to_string(),
+        };
+        format!("{}:{}:{}", sort_name(sort), key, self.id)
+    }
+
+    fn decode(sort: ContentSort, s: &str) -> Option<Self> {
+        let mut parts = s.split(':');
+        if parts.next()? != sort_name(sort) {
+            return None;
+        }
+        let key = parts.next()?;
+        let key = match sort {
+            ContentSort::Rating | ContentSort::Trending => CursorKey::Float(key.parse().ok()?),
+            _ => CursorKey::Int(key.parse().ok()?),
+        };
+        let id = parts.next()?.parse().ok()?;
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Cursor { key, id })
+    }
+}
+
+fn sort_column(sort: ContentSort) -> &'static str {
+    match sort {
+        ContentSort::Recent => "c.created_at",
+        ContentSort::Views => "c.views",
+        ContentSort::Likes => "c.likes",
+        ContentSort::Rating => "c.rating",
+        ContentSort::Trending => "c.trending",
+    }
+}
+
+fn sort_name(sort: ContentSort) -> &'static str {
+    match sort {
+        ContentSort::Recent => "recent",
+        ContentSort::Views => "views",
+        ContentSort::Likes => "likes",
+        ContentSort::Rating => "rating",
+        ContentSort::Trending => "trending",
+    }
+}
+
+fn escape_like(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_should_round_trip() {
+        let cursor = Cursor {
+            key: CursorKey::Int(42),
+            id: 7,
+        };
+        let s = cursor.encode(ContentSort::Views);
+        assert_eq!(s, "views:42:7");
+        assert_eq!(Cursor::decode(ContentSort::Views, &s), Some(cursor));
+        assert_eq!(Cursor::decode(ContentSort::Likes, &s), None);
+        assert_eq!(Cursor::decode(ContentSort::Views, "views:42"), None);
+
+        let cursor = Cursor {
+            key: CursorKey::Float(0.1 + 0.2),
+            id: 7,
+        };
+        let s = cursor.encode(ContentSort::Rating);
+        assert_eq!(Cursor::decode(ContentSort::Rating, &s), Some(cursor));
+        assert_eq!(Cursor::decode(ContentSort::Views, "views:0.5:7"), None);
+    }
+}
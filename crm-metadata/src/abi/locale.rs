@@ -0,0 +1,134 @@
+use chrono::DateTime;
+use minijinja::{State, Value};
+
+/// used when the user has no locale or no variant matches it
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// variants to try in order, `zh-CN` falls back to `zh`
+pub(crate) fn fallbacks(locale: &str) -> Vec<&str> {
+    let mut ret = vec![locale];
+    if let Some((language, _)) = locale.split_once('-') {
+        ret.push(language);
+    }
+    ret
+}
+
+fn language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
+
+/// group digits the way the locale does, zh and ja count large numbers in 万 and 亿/億
+pub(crate) fn format_number(n: i64, locale: &str) -> String {
+    let (ten_thousand, hundred_million) = match language(locale) {
+        "zh" => ("万", "亿"),
+        "ja" => ("万", "億"),
+        _ => return group_digits(n, separator(locale)),
+    };
+    let abs = n.unsigned_abs() as f64;
+    let (value, unit) = if abs >= 1e8 {
+        (n as f64 / 1e8, hundred_million)
+    } else if abs >= 1e4 {
+        (n as f64 / 1e4, ten_thousand)
+    } else {
+        return n.to_string();
+    };
+    let value = format!("{:.1}", value);
+    format!("{}{}", value.trim_end_matches(".0"), unit)
+}
+
+pub(crate) fn format_date(seconds: i64, locale: &str) -> String {
+    let Some(date) = DateTime::from_timestamp(seconds, 0) else {
+        return String::new();
+    };
+    let fmt = match locale {
+        "en-GB" => "%-d %b %Y",
+        _ => match language(locale) {
+            "zh" | "ja" => "%Y年%-m月%-d日",
+            "de" | "ru" | "pl" => "%d.%m.%Y",
+            "fr" | "es" | "it" | "pt" => "%d/%m/%Y",
+            _ => "%b %-d, %Y",
+        },
+    };
+    date.format(fmt).to_string()
+}
+
+fn separator(locale: &str) -> &'static str {
+    match language(locale) {
+        "de" | "es" | "it" | "pt" | "nl" | "id" | "tr" | "da" => ".",
+        "fr" | "ru" | "pl" | "cs" | "sv" | "nb" | "fi" | "uk" => "\u{202f}",
+        _ => ",",
+    }
+}
+
+fn group_digits(n: i64, separator: &str) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut ret = String::with_capacity(digits.len() * 2);
+    if n < 0 {
+        ret.push('-');
+    }
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            ret.push_str(separator);
+        }
+        ret.push(c);
+    }
+    ret
+}
+
+fn current_locale(state: &State) -> String {
+    state
+        .lookup("locale")
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+/// `{{ content.views | number }}`
+pub(crate) fn number_filter(state: &State, value: Value) -> String {
+    match i64::try_from(value.clone()) {
+        Ok(n) => format_number(n, &current_locale(state)),
+        Err(_) => value.to_string(),
+    }
+}
+
+/// `{{ content.created_at | date }}`, takes unix seconds
+pub(crate) fn date_filter(state: &State, value: Value) -> String {
+    if value.is_none() || value.is_undefined() {
+        return String::new();
+    }
+    match i64::try_from(value.clone()) {
+        Ok(seconds) => format_date(seconds, &current_locale(state)),
+        Err(_) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fallbacks_should_work() {
+        assert_eq!(fallbacks("zh-CN"), vec!["zh-CN", "zh"]);
+        assert_eq!(fallbacks("en"), vec!["en"]);
+    }
+
+    #[test]
+    fn format_number_should_work() {
+        assert_eq!(format_number(1234567, "en"), "1,234,567");
+        assert_eq!(format_number(-1234, "de-DE"), "-1.234");
+        assert_eq!(format_number(1234567, "fr"), "1\u{202f}234\u{202f}567");
+        assert_eq!(format_number(999, "zh-CN"), "999");
+        assert_eq!(format_number(123456, "zh-CN"), "12.3万");
+        assert_eq!(format_number(300000000, "zh"), "3亿");
+        assert_eq!(format_number(20000, "ja"), "2万");
+    }
+
+    #[test]
+    fn format_date_should_work() {
+        // 2024-11-20 09:30:00 UTC
+        let seconds = 1732095000;
+        assert_eq!(format_date(seconds, "en"), "Nov 20, 2024");
+        assert_eq!(format_date(seconds, "en-GB"), "20 Nov 2024");
+        assert_eq!(format_date(seconds, "zh-CN"), "2024年11月20日");
+        assert_eq!(format_date(seconds, "de"), "20.11.2024");
+    }
+}
@@ -1,42 +1,88 @@
-use chrono::{DateTime, Days, Utc};
+mod cache;
+mod catalog;
+mod catalog_file;
+mod content;
+mod details;
+mod events;
+mod image;
+mod lifecycle;
+mod listing;
+mod locale;
+mod publisher;
+mod recommend;
+mod related;
+mod series;
+mod store;
+mod tag;
+mod tpl;
+mod watch;
+
+use std::{collections::HashMap, time::Duration};
+
+pub(crate) use cache::ContentCache;
+pub use catalog_file::{read_catalog, write_catalog, CatalogFormat};
+use chrono::Utc;
+use content::internal;
+use lifecycle::unavailable_reason;
+pub(crate) use store::Catalog;
+use store::ContentStore;
+pub use tpl::{Rendered, Tpl};
 
-use fake::{
-    faker::{lorem::zh_cn::Sentence, name::zh_cn::Name},
-    Fake, Faker,
-};
 use futures::{Stream, StreamExt};
-use prost_types::Timestamp;
-use rand::Rng;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Response, Status};
 
 use crate::{
-    pb::{Content, MaterializeRequest, Publisher},
+    pb::{
+        materialize_response::Result as MaterializeResult, Content, MaterializeError,
+        MaterializeRequest, MaterializeResponse,
+    },
     MetaDataService, ResponseStream, ServiceResult,
 };
 
+/// requests looked up together at most
+const BATCH_SIZE: usize = 100;
+/// how long a batch waits for more requests once it has one
+const BATCH_WINDOW: Duration = Duration::from_millis(5);
+
 impl MetaDataService {
     pub async fn materialize(
         &self,
-        mut stream: impl Stream<Item = Result<MaterializeRequest, Status>> + Send + 'static + Unpin,
+        stream: impl Stream<Item = Result<MaterializeRequest, Status>> + Send + 'static + Unpin,
     ) -> ServiceResult<ResponseStream> {
         //使用mpsc通过发送数据，tx负责往通道发送数据，ReceiverStream::new 将rx进行包装之后再使用Box::pin进行包装，返回客户端。
         //客户段 stream.next().await Ok(rx) 获取到rx，然后进行数据的接收
         let (tx, rx) = tokio::sync::mpsc::channel(1024);
+        let svc = self.clone();
         tokio::spawn(async move {
-            while let Some(req) = stream.next().await {
-                match req {
-                    Ok(req) => {
-                        let content = Content::materialize(req.id);
-                        tx.send(Ok(content)).await.unwrap()
+            // requests arriving within the window are looked up together
+            let chunks = tokio_stream::StreamExt::chunks_timeout(stream, BATCH_SIZE, BATCH_WINDOW);
+            let mut chunks = std::pin::pin!(chunks);
+            while let Some(chunk) = chunks.next().await {
+                let mut reqs = Vec::with_capacity(chunk.len());
+                let mut broken = None;
+                for req in chunk {
+                    match req {
+                        Ok(req) => reqs.push(req),
+                        Err(e) => {
+                            broken = Some(e);
+                            break;
+                        }
                     }
-                    Err(e) => tx
-                        .send(Err(Status::internal(format!(
-                            "Failed to fetch data with query {}",
-                            e
-                        ))))
-                        .await
-                        .unwrap(),
+                }
+                let ret = match svc.materialize_batch(&reqs).await {
+                    Ok(responses) => responses.into_iter().map(Ok).collect(),
+                    Err(e) => vec![Err(e)],
+                };
+                for ret in ret {
+                    if tx.send(ret).await.is_err() {
+                        return;
+                    }
+                }
+                // the request stream is broken, pass its status on and stop
+                if let Some(e) = broken {
+                    let _ = tx.send(Err(e)).await;
+                    return;
                 }
             }
         });
@@ -44,80 +90,118 @@ impl MetaDataService {
         let stream = ReceiverStream::new(rx);
         Ok(Response::new(Box::pin(stream)))
     }
-}
 
-impl Content {
-    pub fn materialize(id: u32) -> Self {
-        let mut rng = rand::thread_rng();
-        Content {
-            id,
-            name: Name().fake(),
-            description: Sentence(3..20).fake(),
-            created_at: create_at(),
-            publishers: (1..rng.gen_range(2..10))
-                .map(|_| Publisher::new())
-                .collect(),
-            url: "https://placehold.co/400x400".to_string(),
-            images: "https://placehold.co/400x400".to_string(),
-            content_type: Faker.fake(),
-            views: rng.gen_range(123412..1000000000),
-            likes: rng.gen_range(123333..12313213213),
-            dislikes: rng.gen_range(121313..131212121121),
+    /// answers the requests in order, contents missing from the cache are fetched in one lookup
+    async fn materialize_batch(
+        &self,
+        reqs: &[MaterializeRequest],
+    ) -> Result<Vec<MaterializeResponse>, Status> {
+        let mut ids = reqs.iter().map(|req| req.id).collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        let mut contents = HashMap::with_capacity(ids.len());
+        let mut misses = vec![];
+        for id in ids {
+            match self.cache.get(id) {
+                Some(content) => {
+                    contents.insert(id, content);
+                }
+                None => misses.push(id),
+            }
         }
-    }
-    pub fn content_body(&self) -> String {
-        format!("Content: {:?}", self)
+        if !misses.is_empty() {
+            for content in self
+                .catalog
+                .fetch_contents(&misses)
+                .await
+                .map_err(internal)?
+            {
+                self.cache.insert(content.clone());
+                contents.insert(content.id, content);
+            }
+        }
+
+        let now = Utc::now();
+        let responses = reqs
+            .iter()
+            .map(|req| match contents.get(&req.id) {
+                Some(content) if req.include_unavailable => {
+                    MaterializeResponse::content(content.clone())
+                }
+                Some(content) => match unavailable_reason(content, now) {
+                    Some(reason) => MaterializeResponse::error(req.id, reason),
+                    None => MaterializeResponse::content(content.clone()),
+                },
+                None => MaterializeResponse::error(req.id, "content not found"),
+            })
+            .collect();
+        Ok(responses)
     }
 }
 
-pub struct Tpl<'a>(pub &'a [Content]);
-impl<'a> Tpl<'a> {
-    pub fn to_body(&self) -> String {
-        format!("Tpl: {:?}", self.0)
+impl MaterializeResponse {
+    pub fn content(content: Content) -> Self {
+        MaterializeResponse {
+            result: Some(MaterializeResult::Content(content)),
+        }
     }
-}
-impl Publisher {
-    pub fn new() -> Self {
-        Publisher {
-            id: (10000..200000).fake(),
-            name: Name().fake(),
-            avatar: "https://placehold.co/400x400".to_string(),
+    pub fn error(id: u32, reason: impl Into<String>) -> Self {
+        MaterializeResponse {
+            result: Some(MaterializeResult::Error(MaterializeError {
+                id,
+                reason: reason.into(),
+            })),
         }
     }
 }
-fn before(days: u64) -> DateTime<Utc> {
-    Utc::now().checked_sub_days(Days::new(days)).unwrap()
-}
 
-fn create_at() -> Option<Timestamp> {
-    Some(Timestamp {
-        seconds: before(100).timestamp(),
-        nanos: 0,
-    })
+impl Content {
+    pub fn content_body(&self) -> String {
+        format!("Content: {:?}", self)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
 
-    use crate::AppConfig;
+    use crate::{
+        pb::{ContentStatus, ContentType, CreateContentRequest},
+        AppConfig,
+    };
 
     use super::*;
 
     #[tokio::test]
     async fn test_materialize() -> Result<()> {
-        let service = MetaDataService::new(AppConfig::try_load()?);
-
-        let request_stream = tokio_stream::iter(vec![
-            Ok(MaterializeRequest { id: 1 }),
-            Ok(MaterializeRequest { id: 2 }),
-            Ok(MaterializeRequest { id: 3 }),
-        ]);
+        let service = MetaDataService::try_new(AppConfig::try_load()?).await?;
+        let mut requests = vec![];
+        for name in ["a", "b", "c"] {
+            let req = CreateContentRequest {
+                name: name.to_string(),
+                content_type: ContentType::Short as i32,
+                images: Content::materialize(0).images,
+                status: ContentStatus::Published as i32,
+                ..Default::default()
+            };
+            let content = service.create_content(req).await?.into_inner();
+            requests.push(Ok(MaterializeRequest {
+                id: content.id,
+                ..Default::default()
+            }));
+        }
+        let request_stream = tokio_stream::iter(requests);
 
         let response = service.materialize(request_stream).await.unwrap();
         let response = response.into_inner();
         let content = response.collect::<Vec<_>>().await;
         assert_eq!(content.len(), 3);
+        assert!(content.iter().all(|c| matches!(
+            c,
+            Ok(MaterializeResponse {
+                result: Some(MaterializeResult::Content(_))
+            })
+        )));
         Ok(())
     }
 }
@@ -0,0 +1,195 @@
+use serde::Deserialize;
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{
+        CreatePublisherRequest, DeletePublisherRequest, DeletePublisherResponse, FollowRequest,
+        FollowResponse, GetPublisherRequest, ListFollowedRequest, ListFollowedResponse,
+        ListPublishersRequest, ListPublishersResponse, NewFromFollowedRequest,
+        NewFromFollowedResponse, Publisher, UpdatePublisherRequest,
+    },
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{internal, ts_to_utc, ContentRow, CONTENT_SELECT};
+
+const DEFAULT_N: usize = 10;
+const MAX_N: usize = 100;
+
+/// a content is released once it's published, or at its publish_at when that was scheduled later
+const RELEASED_AT: &str = "greatest(c.published_at, coalesce(c.publish_at, c.published_at))";
+
+#[derive(Debug, Deserialize, sqlx::FromRow)]
+pub(crate) struct PublisherRow {
+    id: i32,
+    name: String,
+    avatar: String,
+}
+
+impl MetaDataService {
+    pub async fn create_publisher(&self, req: CreatePublisherRequest) -> ServiceResult<Publisher> {
+        let row = sqlx::query_as::<_, PublisherRow>(
+            "insert into publishers(name, avatar) values($1, $2) returning id, name, avatar",
+        )
+        .bind(&req.name)
+        .bind(&req.avatar)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(internal)?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn get_publisher(&self, req: GetPublisherRequest) -> ServiceResult<Publisher> {
+        let row = sqlx::query_as::<_, PublisherRow>(
+            "select id, name, avatar from publishers where id = $1",
+        )
+        .bind(req.id as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| publisher_not_found(req.id))?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn update_publisher(&self, req: UpdatePublisherRequest) -> ServiceResult<Publisher> {
+        let row = sqlx::query_as::<_, PublisherRow>(
+            "update publishers set name = $2, avatar = $3 where id = $1 returning id, name, avatar",
+        )
+        .bind(req.id as i32)
+        .bind(&req.name)
+        .bind(&req.avatar)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| publisher_not_found(req.id))?;
+        self.cache.clear();
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn delete_publisher(
+        &self,
+        req: DeletePublisherRequest,
+    ) -> ServiceResult<DeletePublisherResponse> {
+        let ret = sqlx::query("delete from publishers where id = $1")
+            .bind(req.id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(publisher_not_found(req.id));
+        }
+        self.cache.clear();
+        Ok(Response::new(DeletePublisherResponse { id: req.id }))
+    }
+
+    pub async fn list_publishers(
+        &self,
+        req: ListPublishersRequest,
+    ) -> ServiceResult<ListPublishersResponse> {
+        let n = match req.n as usize {
+            0 => DEFAULT_N,
+            n => n.min(MAX_N),
+        };
+        let rows = sqlx::query_as::<_, PublisherRow>(
+            "select id, name, avatar from publishers where id > $1 order by id limit $2",
+        )
+        .bind(req.after_id as i32)
+        .bind(n as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        let publishers = rows.into_iter().map(Into::into).collect();
+        Ok(Response::new(ListPublishersResponse { publishers }))
+    }
+
+    pub async fn follow_publisher(&self, req: FollowRequest) -> ServiceResult<FollowResponse> {
+        if req.email.is_empty() {
+            return Err(Status::invalid_argument("email is required"));
+        }
+        sqlx::query(
+            "insert into publisher_follows(email, publisher_id) values($1, $2) on conflict do nothing",
+        )
+        .bind(&req.email)
+        .bind(req.publisher_id as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                publisher_not_found(req.publisher_id)
+            }
+            e => internal(e),
+        })?;
+        Ok(Response::new(FollowResponse {}))
+    }
+
+    pub async fn unfollow_publisher(&self, req: FollowRequest) -> ServiceResult<FollowResponse> {
+        sqlx::query("delete from publisher_follows where email = $1 and publisher_id = $2")
+            .bind(&req.email)
+            .bind(req.publisher_id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(internal)?;
+        Ok(Response::new(FollowResponse {}))
+    }
+
+    pub async fn list_followed(
+        &self,
+        req: ListFollowedRequest,
+    ) -> ServiceResult<ListFollowedResponse> {
+        let rows = sqlx::query_as::<_, PublisherRow>(
+            r#"select p.id, p.name, p.avatar from publisher_follows f
+            join publishers p on p.id = f.publisher_id where f.email = $1 order by p.name, p.id"#,
+        )
+        .bind(&req.email)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        let publishers = rows.into_iter().map(Into::into).collect();
+        Ok(Response::new(ListFollowedResponse { publishers }))
+    }
+
+    /// what the followed publishers released since the user was last told, for "new from creators you follow"
+    pub async fn new_from_followed(
+        &self,
+        req: NewFromFollowedRequest,
+    ) -> ServiceResult<NewFromFollowedResponse> {
+        let n = match req.n as usize {
+            0 => DEFAULT_N,
+            n => n.min(MAX_N),
+        };
+        let since = req.since.as_ref().and_then(ts_to_utc);
+        let rows = sqlx::query_as::<_, ContentRow>(&format!(
+            r#"{select}
+            where c.id in (select cp.content_id from content_publishers cp
+                join publisher_follows f on f.publisher_id = cp.publisher_id where f.email = $1)
+              and content_available(c) and ($2::timestamptz is null or {released_at} > $2)
+            group by c.id
+            order by {released_at} desc, c.id desc
+            limit $3"#,
+            select = CONTENT_SELECT,
+            released_at = RELEASED_AT,
+        ))
+        .bind(&req.email)
+        .bind(since)
+        .bind(n as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        let contents = rows.into_iter().map(Into::into).collect();
+        Ok(Response::new(NewFromFollowedResponse { contents }))
+    }
+}
+
+fn publisher_not_found(id: u32) -> Status {
+    Status::not_found(format!("Publisher {} not found", id))
+}
+
+impl From<PublisherRow> for Publisher {
+    fn from(row: PublisherRow) -> Self {
+        Publisher {
+            id: row.id as _,
+            name: row.name,
+            avatar: row.avatar,
+        }
+    }
+}
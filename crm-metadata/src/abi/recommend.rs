@@ -0,0 +1,58 @@
+use tonic::Response;
+use user_state::pb::GetHistoryRequest;
+
+use crate::{
+    pb::{RecommendRequest, RecommendResponse},
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{fetch_contents, internal};
+
+const DEFAULT_N: usize = 10;
+const MAX_N: usize = 100;
+
+/// popularity is damped with ln so a few huge hits don't drown the history signals,
+/// each signal (same content type, shared publisher with anything the user watched)
+/// adds one more multiple of it
+const RECOMMEND_SQL: &str = r#"with seeds as (select id, content_type from contents where id = any($1)),
+seed_publishers as (select distinct publisher_id from content_publishers where content_id = any($1))
+select c.id from contents c
+where c.id <> all($2) and content_available(c)
+order by (1 + ln(1 + c.views) + 2 * ln(1 + c.likes))
+  * (1 + (c.content_type in (select content_type from seeds))::int
+     + (exists (select 1 from content_publishers cp
+        where cp.content_id = c.id and cp.publisher_id in (select publisher_id from seed_publishers)))::int) desc,
+  c.id desc
+limit $3"#;
+
+impl MetaDataService {
+    pub async fn recommend(&self, req: RecommendRequest) -> ServiceResult<RecommendResponse> {
+        let n = match req.n as usize {
+            0 => DEFAULT_N,
+            n => n.min(MAX_N),
+        };
+        let history = self
+            .user_stats
+            .clone()
+            .get_history(GetHistoryRequest { email: req.email })
+            .await?
+            .into_inner();
+
+        let to_i32 = |ids: &[u32]| ids.iter().map(|id| *id as i32).collect::<Vec<_>>();
+        let seeds = to_i32(&[history.recent_watched, history.finished.clone()].concat());
+        let finished = to_i32(&history.finished);
+        let ids: Vec<i32> = sqlx::query_scalar(RECOMMEND_SQL)
+            .bind(seeds)
+            .bind(finished)
+            .bind(n as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(internal)?;
+
+        // fetch_contents doesn't keep the ranking, restore it by id
+        let ids = ids.into_iter().map(|id| id as u32).collect::<Vec<_>>();
+        let mut contents = fetch_contents(&self.pool, &ids).await.map_err(internal)?;
+        contents.sort_by_key(|c| ids.iter().position(|id| *id == c.id));
+        Ok(Response::new(RecommendResponse { contents }))
+    }
+}
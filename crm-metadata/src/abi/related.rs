@@ -0,0 +1,50 @@
+use tonic::Response;
+
+use crate::{
+    pb::{RelatedContent, RelatedContentRequest, RelatedContentResponse},
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{fetch_contents, internal};
+
+const DEFAULT_N: usize = 10;
+const MAX_N: usize = 100;
+
+impl MetaDataService {
+    /// neighbors precomputed by the similarity job, ids no longer available are left out
+    pub async fn related_content(
+        &self,
+        req: RelatedContentRequest,
+    ) -> ServiceResult<RelatedContentResponse> {
+        let n = match req.n as usize {
+            0 => DEFAULT_N,
+            n => n.min(MAX_N),
+        };
+        let neighbors: Vec<(i32, f64)> = sqlx::query_as(
+            r#"select r.related_id, r.score from related_contents r join contents c on c.id = r.related_id
+            where r.content_id = $1 and content_available(c) order by r.score desc, r.related_id limit $2"#,
+        )
+        .bind(req.id as i32)
+        .bind(n as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+
+        let ids = neighbors
+            .iter()
+            .map(|(id, _)| *id as u32)
+            .collect::<Vec<_>>();
+        let mut contents = fetch_contents(&self.pool, &ids).await.map_err(internal)?;
+        let related = neighbors
+            .into_iter()
+            .filter_map(|(id, score)| {
+                let pos = contents.iter().position(|c| c.id == id as u32)?;
+                Some(RelatedContent {
+                    content: Some(contents.swap_remove(pos)),
+                    score,
+                })
+            })
+            .collect();
+        Ok(Response::new(RelatedContentResponse { related }))
+    }
+}
@@ -0,0 +1,256 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use tonic::{Response, Status};
+use user_state::pb::GetHistoryRequest;
+
+use crate::{
+    pb::{
+        AddEpisodeRequest, Content, CreateSeriesRequest, Episode, ListEpisodesRequest,
+        ListEpisodesResponse, NextUp, NextUpRequest, NextUpResponse, Series,
+    },
+    MetaDataService, ServiceResult,
+};
+
+use super::content::{fetch_contents, internal, utc_to_ts};
+
+#[derive(Debug, sqlx::FromRow)]
+struct SeriesRow {
+    id: i32,
+    name: String,
+    description: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+struct EpisodeRow {
+    series_id: i32,
+    season: i32,
+    episode: i32,
+    content_id: i32,
+    available: bool,
+}
+
+impl MetaDataService {
+    pub async fn create_series(&self, req: CreateSeriesRequest) -> ServiceResult<Series> {
+        let row = sqlx::query_as::<_, SeriesRow>(
+            "insert into series(name, description) values($1, $2) returning id, name, description, created_at",
+        )
+        .bind(&req.name)
+        .bind(&req.description)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(internal)?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn add_episode(&self, req: AddEpisodeRequest) -> ServiceResult<Episode> {
+        sqlx::query(
+            "insert into series_episodes(series_id, season, episode, content_id) values($1, $2, $3, $4)",
+        )
+        .bind(req.series_id as i32)
+        .bind(req.season as i32)
+        .bind(req.episode as i32)
+        .bind(req.content_id as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => Status::not_found(format!(
+                "Series {} or content {} not found",
+                req.series_id, req.content_id
+            )),
+            sqlx::Error::Database(e) if e.is_unique_violation() => Status::already_exists(format!(
+                "Episode or content already in a series: {}",
+                e
+            )),
+            e => internal(e),
+        })?;
+        let content = fetch_contents(&self.pool, &[req.content_id])
+            .await
+            .map_err(internal)?
+            .pop();
+        Ok(Response::new(Episode {
+            series_id: req.series_id,
+            season: req.season,
+            episode: req.episode,
+            content,
+        }))
+    }
+
+    pub async fn list_episodes(
+        &self,
+        req: ListEpisodesRequest,
+    ) -> ServiceResult<ListEpisodesResponse> {
+        let series = self
+            .fetch_series(&[req.series_id as i32])
+            .await?
+            .pop()
+            .ok_or_else(|| Status::not_found(format!("Series {} not found", req.series_id)))?;
+        let rows = sqlx::query_as::<_, EpisodeRow>(
+            r#"select e.series_id, e.season, e.episode, e.content_id, content_available(c) as available
+            from series_episodes e join contents c on c.id = e.content_id
+            where e.series_id = $1 order by e.season, e.episode"#,
+        )
+        .bind(req.series_id as i32)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        let episodes = self.episodes(&rows).await?;
+        Ok(Response::new(ListEpisodesResponse {
+            series: Some(series),
+            episodes,
+        }))
+    }
+
+    /// the next episode to watch in every series the user has watched any of
+    pub async fn next_up(&self, req: NextUpRequest) -> ServiceResult<NextUpResponse> {
+        let history = self
+            .user_stats
+            .clone()
+            .get_history(GetHistoryRequest { email: req.email })
+            .await?
+            .into_inner();
+        let to_set = |ids: &[u32]| ids.iter().map(|id| *id as i32).collect::<HashSet<_>>();
+        let finished = to_set(&history.finished);
+        let started = to_set(&history.started_but_not_finished);
+        let watched = finished.union(&started).copied().collect::<Vec<_>>();
+
+        let rows = sqlx::query_as::<_, EpisodeRow>(
+            r#"select e.series_id, e.season, e.episode, e.content_id, content_available(c) as available
+            from series_episodes e join contents c on c.id = e.content_id
+            where e.series_id in (select series_id from series_episodes where content_id = any($1))
+            order by e.series_id, e.season, e.episode"#,
+        )
+        .bind(&watched)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+
+        let mut picked = vec![];
+        for episodes in rows.chunk_by(|a, b| a.series_id == b.series_id) {
+            if let Some((episode, resume)) = pick_next(episodes, &finished, &started) {
+                picked.push((episode.clone(), resume));
+            }
+        }
+        let series_ids = picked.iter().map(|(e, _)| e.series_id).collect::<Vec<_>>();
+        let mut series = self
+            .fetch_series(&series_ids)
+            .await?
+            .into_iter()
+            .map(|s| (s.id, s))
+            .collect::<HashMap<_, _>>();
+        let rows = picked.iter().map(|(e, _)| e.clone()).collect::<Vec<_>>();
+        let next_up = self
+            .episodes(&rows)
+            .await?
+            .into_iter()
+            .zip(picked)
+            .map(|(episode, (_, resume))| NextUp {
+                series: series.remove(&episode.series_id),
+                episode: Some(episode),
+                resume,
+            })
+            .collect();
+        Ok(Response::new(NextUpResponse { next_up }))
+    }
+
+    async fn fetch_series(&self, ids: &[i32]) -> Result<Vec<Series>, Status> {
+        let rows = sqlx::query_as::<_, SeriesRow>(
+            "select id, name, description, created_at from series where id = any($1)",
+        )
+        .bind(ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// attach the contents to the episodes, keeping their order
+    async fn episodes(&self, rows: &[EpisodeRow]) -> Result<Vec<Episode>, Status> {
+        let ids = rows.iter().map(|e| e.content_id as u32).collect::<Vec<_>>();
+        let mut contents: HashMap<u32, Content> = fetch_contents(&self.pool, &ids)
+            .await
+            .map_err(internal)?
+            .into_iter()
+            .map(|c| (c.id, c))
+            .collect();
+        Ok(rows
+            .iter()
+            .map(|e| Episode {
+                series_id: e.series_id as _,
+                season: e.season as _,
+                episode: e.episode as _,
+                content: contents.remove(&(e.content_id as u32)),
+            })
+            .collect())
+    }
+}
+
+/// resume the latest started episode after the last finished one, otherwise start the one after it,
+/// episodes are in watching order and unavailable ones are skipped
+fn pick_next<'a>(
+    episodes: &'a [EpisodeRow],
+    finished: &HashSet<i32>,
+    started: &HashSet<i32>,
+) -> Option<(&'a EpisodeRow, bool)> {
+    let after = episodes
+        .iter()
+        .rposition(|e| finished.contains(&e.content_id))
+        .map_or(0, |pos| pos + 1);
+    let rest = &episodes[after..];
+    if let Some(e) = rest
+        .iter()
+        .rev()
+        .find(|e| e.available && started.contains(&e.content_id))
+    {
+        return Some((e, true));
+    }
+    rest.iter().find(|e| e.available).map(|e| (e, false))
+}
+
+impl From<SeriesRow> for Series {
+    fn from(row: SeriesRow) -> Self {
+        Series {
+            id: row.id as _,
+            name: row.name,
+            description: row.description,
+            created_at: Some(utc_to_ts(row.created_at)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn episodes(available: &[bool]) -> Vec<EpisodeRow> {
+        available
+            .iter()
+            .enumerate()
+            .map(|(i, available)| EpisodeRow {
+                series_id: 1,
+                season: 1,
+                episode: i as i32 + 1,
+                content_id: i as i32 + 1,
+                available: *available,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pick_next_should_work() {
+        let eps = episodes(&[true, true, false, true]);
+        let set = |ids: &[i32]| ids.iter().copied().collect::<HashSet<_>>();
+        let pick = |finished: &[i32], started: &[i32]| {
+            pick_next(&eps, &set(finished), &set(started)).map(|(e, resume)| (e.content_id, resume))
+        };
+        assert_eq!(pick(&[1], &[]), Some((2, false)));
+        assert_eq!(pick(&[1], &[2]), Some((2, true)));
+        // the unavailable episode 3 is skipped
+        assert_eq!(pick(&[1, 2], &[]), Some((4, false)));
+        // started before the last finished one doesn't count any more
+        assert_eq!(pick(&[2], &[1]), Some((4, false)));
+        assert_eq!(pick(&[], &[4]), Some((4, true)));
+        assert_eq!(pick(&[4], &[]), None);
+    }
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Days};
+use fake::{
+    faker::{lorem::zh_cn::Sentence, name::zh_cn::Name},
+    Fake, Faker,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use sqlx::PgPool;
+
+use crate::{
+    config::CatalogBackend,
+    pb::{Content, ContentStatus, Image, ImageFormat, ImageRole, Publisher},
+};
+
+use super::content::{fetch_contents, utc_to_ts};
+
+/// fake contents are created within a year before this, so they don't drift with the clock
+const FAKE_EPOCH: i64 = 1_700_000_000;
+
+/// where Materialize looks contents up
+pub(crate) trait ContentStore {
+    /// unknown ids are left out
+    async fn fetch_contents(&self, ids: &[u32]) -> Result<Vec<Content>, sqlx::Error>;
+}
+
+/// the catalog in postgres
+#[derive(Clone)]
+pub(crate) struct PgCatalog {
+    pool: PgPool,
+}
+
+/// every id exists and always yields the same content, for tests and demos
+#[derive(Clone)]
+pub(crate) struct FakeCatalog;
+
+#[derive(Clone)]
+pub(crate) enum Catalog {
+    Postgres(PgCatalog),
+    Fake(FakeCatalog),
+}
+
+impl Catalog {
+    pub(crate) fn new(backend: CatalogBackend, pool: PgPool) -> Self {
+        match backend {
+            CatalogBackend::Postgres => Catalog::Postgres(PgCatalog { pool }),
+            CatalogBackend::Fake => Catalog::Fake(FakeCatalog),
+        }
+    }
+}
+
+impl ContentStore for Catalog {
+    async fn fetch_contents(&self, ids: &[u32]) -> Result<Vec<Content>, sqlx::Error> {
+        match self {
+            Catalog::Postgres(catalog) => catalog.fetch_contents(ids).await,
+            Catalog::Fake(catalog) => catalog.fetch_contents(ids).await,
+        }
+    }
+}
+
+impl ContentStore for PgCatalog {
+    async fn fetch_contents(&self, ids: &[u32]) -> Result<Vec<Content>, sqlx::Error> {
+        fetch_contents(&self.pool, ids).await
+    }
+}
+
+impl ContentStore for FakeCatalog {
+    async fn fetch_contents(&self, ids: &[u32]) -> Result<Vec<Content>, sqlx::Error> {
+        Ok(ids.iter().map(|id| Content::materialize(*id)).collect())
+    }
+}
+
+impl Content {
+    /// a fake content seeded from the id, the same id always yields the same content
+    pub fn materialize(id: u32) -> Self {
+        let mut rng = StdRng::seed_from_u64(id as u64);
+        let created_at = DateTime::from_timestamp(FAKE_EPOCH, 0)
+            .and_then(|t| t.checked_sub_days(Days::new(rng.gen_range(0..365))))
+            .map(utc_to_ts);
+        Content {
+            id,
+            name: Name().fake_with_rng(&mut rng),
+            description: Sentence(3..20).fake_with_rng(&mut rng),
+            created_at,
+            publishers: (1..rng.gen_range(2..10))
+                .map(|_| Publisher::materialize(&mut rng))
+                .collect(),
+            url: "https://placehold.co/400x400".to_string(),
+            images: vec![Image {
+                role: ImageRole::Thumbnail as i32,
+                url: "https://placehold.co/400x400".to_string(),
+                width: 400,
+                height: 400,
+                format: ImageFormat::Png as i32,
+            }],
+            content_type: Faker.fake_with_rng(&mut rng),
+            views: rng.gen_range(123412..1000000000),
+            likes: rng.gen_range(123333..12313213213),
+            dislikes: rng.gen_range(121313..131212121121),
+            status: ContentStatus::Published as i32,
+            ..Default::default()
+        }
+    }
+}
+
+impl Publisher {
+    fn materialize(rng: &mut impl Rng) -> Self {
+        Publisher {
+            id: (10000..200000).fake_with_rng(rng),
+            name: Name().fake_with_rng(rng),
+            avatar: "https://placehold.co/400x400".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn fake_catalog_should_be_deterministic() {
+        let contents = FakeCatalog.fetch_contents(&[1, 2, 1]).await.unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[0], contents[2]);
+        assert_ne!(contents[0].name, contents[1].name);
+        assert_eq!(contents[1].id, 2);
+        assert!(!contents[1].publishers.is_empty());
+    }
+}
@@ -0,0 +1,217 @@
+use serde::Deserialize;
+use sqlx::{Postgres, Transaction};
+use tonic::{Response, Status};
+use user_state::pb::GetHistoryRequest;
+
+use crate::{
+    pb::{
+        CreateTagRequest, DeleteTagRequest, DeleteTagResponse, ListTagsRequest, ListTagsResponse,
+        Tag, TagAffinity, TagAffinityRequest, TagAffinityResponse, TagKind, UpdateTagRequest,
+    },
+    MetaDataService, ServiceResult,
+};
+
+use super::content::internal;
+
+const DEFAULT_N: usize = 10;
+const MAX_N: usize = 100;
+
+/// how much a content counts towards the affinity by how far the user got into it
+const FINISHED_WEIGHT: f64 = 1.0;
+const STARTED_WEIGHT: f64 = 0.5;
+const VIEWED_WEIGHT: f64 = 0.25;
+
+/// share of the user's watched contents (by weight) carrying each tag,
+/// contents no longer in the catalog don't count
+const AFFINITY_SQL: &str = r#"with watched as (
+  select u.id, max(u.weight) as weight from unnest($1::int[], $2::float8[]) as u(id, weight)
+  join contents c on c.id = u.id group by u.id
+)
+select t.id, t.name, t.kind::text as kind,
+  sum(w.weight) / (select sum(weight) from watched) as score, count(*) as contents
+from watched w
+  join content_tags ct on ct.content_id = w.id
+  join tags t on t.id = ct.tag_id
+group by t.id
+order by score desc, t.id
+limit $3"#;
+
+#[derive(Debug, Deserialize, sqlx::FromRow)]
+pub(crate) struct TagRow {
+    id: i32,
+    name: String,
+    kind: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AffinityRow {
+    #[sqlx(flatten)]
+    tag: TagRow,
+    score: f64,
+    contents: i64,
+}
+
+impl MetaDataService {
+    pub async fn create_tag(&self, req: CreateTagRequest) -> ServiceResult<Tag> {
+        let row = sqlx::query_as::<_, TagRow>(
+            "insert into tags(name, kind) values($1, $2::tag_kind) returning id, name, kind::text as kind",
+        )
+        .bind(&req.name)
+        .bind(tag_kind_name(req.kind()))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| tag_error(e, &req.name))?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn update_tag(&self, req: UpdateTagRequest) -> ServiceResult<Tag> {
+        let row = sqlx::query_as::<_, TagRow>(
+            "update tags set name = $2, kind = $3::tag_kind where id = $1 returning id, name, kind::text as kind",
+        )
+        .bind(req.id as i32)
+        .bind(&req.name)
+        .bind(tag_kind_name(req.kind()))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| tag_error(e, &req.name))?
+        .ok_or_else(|| Status::not_found(format!("Tag {} not found", req.id)))?;
+        self.cache.clear();
+        Ok(Response::new(row.into()))
+    }
+
+    /// the tag is unassigned from all of its contents
+    pub async fn delete_tag(&self, req: DeleteTagRequest) -> ServiceResult<DeleteTagResponse> {
+        let ret = sqlx::query("delete from tags where id = $1")
+            .bind(req.id as i32)
+            .execute(&self.pool)
+            .await
+            .map_err(internal)?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("Tag {} not found", req.id)));
+        }
+        self.cache.clear();
+        Ok(Response::new(DeleteTagResponse { id: req.id }))
+    }
+
+    pub async fn list_tags(&self, req: ListTagsRequest) -> ServiceResult<ListTagsResponse> {
+        let kind = req.kind.map(|_| tag_kind_name(req.kind()));
+        let rows = sqlx::query_as::<_, TagRow>(
+            "select id, name, kind::text as kind from tags where $1::tag_kind is null or kind = $1::tag_kind order by name",
+        )
+        .bind(kind)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        Ok(Response::new(ListTagsResponse {
+            tags: rows.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// which tags the user watches most, e.g. to pick the users who love documentaries
+    pub async fn tag_affinity(
+        &self,
+        req: TagAffinityRequest,
+    ) -> ServiceResult<TagAffinityResponse> {
+        let n = match req.n as usize {
+            0 => DEFAULT_N,
+            n => n.min(MAX_N),
+        };
+        let history = self
+            .user_stats
+            .clone()
+            .get_history(GetHistoryRequest { email: req.email })
+            .await?
+            .into_inner();
+        let mut ids = vec![];
+        let mut weights = vec![];
+        for (watched, weight) in [
+            (&history.finished, FINISHED_WEIGHT),
+            (&history.started_but_not_finished, STARTED_WEIGHT),
+            (&history.recent_watched, STARTED_WEIGHT),
+            (&history.viewed_but_not_started, VIEWED_WEIGHT),
+        ] {
+            for id in watched {
+                ids.push(*id as i32);
+                weights.push(weight);
+            }
+        }
+
+        let rows = sqlx::query_as::<_, AffinityRow>(AFFINITY_SQL)
+            .bind(ids)
+            .bind(weights)
+            .bind(n as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(internal)?;
+        let affinities = rows
+            .into_iter()
+            .map(|row| TagAffinity {
+                tag: Some(row.tag.into()),
+                score: row.score,
+                contents: row.contents as _,
+            })
+            .collect();
+        Ok(Response::new(TagAffinityResponse { affinities }))
+    }
+}
+
+pub(crate) async fn set_tags(
+    tx: &mut Transaction<'_, Postgres>,
+    content_id: i32,
+    tag_ids: &[u32],
+) -> Result<(), Status> {
+    sqlx::query("delete from content_tags where content_id = $1")
+        .bind(content_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(internal)?;
+    let tag_ids = tag_ids.iter().map(|id| *id as i32).collect::<Vec<_>>();
+    sqlx::query(
+        "insert into content_tags(content_id, tag_id) select $1, unnest($2::int[]) on conflict do nothing",
+    )
+    .bind(content_id)
+    .bind(tag_ids)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+            Status::invalid_argument(format!("Unknown tag: {}", e))
+        }
+        e => internal(e),
+    })?;
+    Ok(())
+}
+
+fn tag_error(e: sqlx::Error, name: &str) -> Status {
+    match e {
+        sqlx::Error::Database(e) if e.is_unique_violation() => {
+            Status::already_exists(format!("Tag {} already exists", name))
+        }
+        e => internal(e),
+    }
+}
+
+pub(crate) fn tag_kind_name(kind: TagKind) -> &'static str {
+    match kind {
+        TagKind::Unspecified | TagKind::Tag => "tag",
+        TagKind::Genre => "genre",
+    }
+}
+
+pub(crate) fn tag_kind_from_name(name: &str) -> TagKind {
+    match name {
+        "tag" => TagKind::Tag,
+        "genre" => TagKind::Genre,
+        _ => TagKind::Unspecified,
+    }
+}
+
+impl From<TagRow> for Tag {
+    fn from(row: TagRow) -> Self {
+        Tag {
+            id: row.id as _,
+            name: row.name,
+            kind: tag_kind_from_name(&row.kind) as i32,
+        }
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::BTreeSet;
+
+use minijinja::{context, Environment, Error, UndefinedBehavior};
+use serde::Serialize;
+use user_state::pb::User;
+
+use crate::pb::{content::Details, Content};
+
+use super::image::{image_filter, ImageRow};
+use super::locale::{date_filter, fallbacks, number_filter, DEFAULT_LOCALE};
+
+/// built-in templates as (name, locale, subject, html, text), no locale is the fallback variant
+const BUILTINS: &[(&str, Option<&str>, &str, &str, &str)] = &[
+    (
+        "welcome",
+        None,
+        "Welcome",
+        include_str!("../../templates/welcome.html"),
+        include_str!("../../templates/welcome.txt"),
+    ),
+    (
+        "welcome",
+        Some("zh"),
+        "欢迎",
+        include_str!("../../templates/welcome.zh.html"),
+        include_str!("../../templates/welcome.zh.txt"),
+    ),
+    (
+        "recall",
+        None,
+        "Recall",
+        include_str!("../../templates/recall.html"),
+        include_str!("../../templates/recall.txt"),
+    ),
+    (
+        "recall",
+        Some("zh"),
+        "好久不见",
+        include_str!("../../templates/recall.zh.html"),
+        include_str!("../../templates/recall.zh.txt"),
+    ),
+    (
+        "remind",
+        None,
+        "Remind!!!",
+        include_str!("../../templates/remind.html"),
+        include_str!("../../templates/remind.txt"),
+    ),
+    (
+        "remind",
+        Some("zh"),
+        "别忘了继续观看",
+        include_str!("../../templates/remind.zh.html"),
+        include_str!("../../templates/remind.zh.txt"),
+    ),
+];
+
+/// named email templates rendered with `user`, `contents` and `locale` in scope
+pub struct Tpl {
+    env: Environment<'static>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rendered {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+struct UserView<'a> {
+    email: &'a str,
+    name: &'a str,
+    phone: Option<&'a str>,
+    locale: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+struct ContentView<'a> {
+    id: u32,
+    name: &'a str,
+    description: &'a str,
+    url: &'a str,
+    /// pick a variant with the `image` filter, e.g. `content.images | image("banner", "email")`
+    images: Vec<ImageRow>,
+    content_type: &'a str,
+    publishers: Vec<&'a str>,
+    /// unix seconds, format with the `date` filter
+    created_at: Option<i64>,
+    views: u64,
+    likes: u64,
+    dislikes: u64,
+    /// tagged with `kind`, e.g. `{% if content.details.kind == "movie" %}`
+    details: Option<&'a Details>,
+}
+
+impl Tpl {
+    /// the built-in templates
+    pub fn new() -> Self {
+        let mut tpl = Self::empty();
+        for (name, locale, subject, html, text) in BUILTINS {
+            let ret = match locale {
+                Some(locale) => tpl.add_locale(name, locale, subject, html, text),
+                None => tpl.add(name, subject, html, text),
+            };
+            ret.expect("built-in templates should compile");
+        }
+        tpl
+    }
+
+    /// no templates, only the filters, so a stored template can't pick up a built-in variant
+    pub fn empty() -> Self {
+        let mut env = Environment::new();
+        // a typo in a template should fail the render instead of sending blanks
+        env.set_undefined_behavior(UndefinedBehavior::Strict);
+        env.add_filter("number", number_filter);
+        env.add_filter("date", date_filter);
+        env.add_filter("image", image_filter);
+        Tpl { env }
+    }
+
+    /// add or replace the fallback variant of the template with the given name
+    pub fn add(&mut self, name: &str, subject: &str, html: &str, text: &str) -> Result<(), Error> {
+        self.add_parts(name.to_string(), subject, html, text)
+    }
+
+    /// add or replace the variant used for users of the locale, `zh` also serves `zh-CN`
+    pub fn add_locale(
+        &mut self,
+        name: &str,
+        locale: &str,
+        subject: &str,
+        html: &str,
+        text: &str,
+    ) -> Result<(), Error> {
+        self.add_parts(format!("{}.{}", name, locale), subject, html, text)
+    }
+
+    /// the user's locale picks the variant, falling back to the language and then the default
+    pub fn render(&self, name: &str, user: &User, contents: &[Content]) -> Result<Rendered, Error> {
+        let locale = user.locale.as_deref().unwrap_or(DEFAULT_LOCALE);
+        let variant = fallbacks(locale)
+            .into_iter()
+            .map(|l| format!("{}.{}", name, l))
+            .find(|v| self.env.get_template(&format!("{}.html", v)).is_ok())
+            .unwrap_or_else(|| name.to_string());
+        let ctx = context! {
+            user => UserView::from(user),
+            contents => contents.iter().map(ContentView::from).collect::<Vec<_>>(),
+            locale => locale,
+        };
+        // html is escaped by the .html extension, subject and text are left as is
+        let subject = self
+            .env
+            .get_template(&format!("{}.subject", variant))?
+            .render(&ctx)?;
+        let html = self
+            .env
+            .get_template(&format!("{}.html", variant))?
+            .render(&ctx)?;
+        let text = self
+            .env
+            .get_template(&format!("{}.txt", variant))?
+            .render(&ctx)?;
+        Ok(Rendered {
+            subject,
+            html,
+            text,
+        })
+    }
+
+    /// names of all the templates that can be rendered
+    pub fn names(&self) -> Vec<String> {
+        let names: BTreeSet<_> = self
+            .env
+            .templates()
+            .filter_map(|(name, _)| name.strip_suffix(".html"))
+            .filter(|name| !name.contains('.'))
+            .map(|name| name.to_string())
+            .collect();
+        names.into_iter().collect()
+    }
+
+    fn add_parts(
+        &mut self,
+        name: String,
+        subject: &str,
+        html: &str,
+        text: &str,
+    ) -> Result<(), Error> {
+        self.env
+            .add_template_owned(format!("{}.subject", name), subject.to_string())?;
+        self.env
+            .add_template_owned(format!("{}.html", name), html.to_string())?;
+        self.env
+            .add_template_owned(format!("{}.txt", name), text.to_string())?;
+        Ok(())
+    }
+}
+
+impl Default for Tpl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> From<&'a User> for UserView<'a> {
+    fn from(user: &'a User) -> Self {
+        UserView {
+            email: &user.email,
+            name: &user.name,
+            phone: user.phone.as_deref(),
+            locale: user.locale.as_deref(),
+        }
+    }
+}
+
+impl<'a> From<&'a Content> for ContentView<'a> {
+    fn from(content: &'a Content) -> Self {
+        ContentView {
+            id: content.id,
+            name: &content.name,
+            description: &content.description,
+            url: &content.url,
+            images: content.images.iter().map(ImageRow::from).collect(),
+            content_type: content.content_type().as_str_name(),
+            publishers: content.publishers.iter().map(|p| p.name.as_str()).collect(),
+            created_at: content.created_at.as_ref().map(|ts| ts.seconds),
+            views: content.views,
+            likes: content.likes,
+            dislikes: content.dislikes,
+            details: content.details.as_ref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pb::{Image, ImageFormat, ImageRole, MovieDetails, VlogDetails};
+
+    fn user(locale: Option<&str>) -> User {
+        User {
+            email: "tyr@example.com".to_string(),
+            name: "Tyr".to_string(),
+            locale: locale.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn content() -> Content {
+        Content {
+            name: "<Rust>".to_string(),
+            url: "https://example.com/1".to_string(),
+            views: 1234567,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn render_should_work() {
+        let tpl = Tpl::new();
+        let ret = tpl.render("welcome", &user(None), &[content()]).unwrap();
+        assert_eq!(ret.subject, "Welcome");
+        assert!(ret.html.contains("Hi Tyr,"));
+        assert!(ret.html.contains("&lt;Rust&gt;"));
+        assert!(ret.text.contains("- <Rust>"));
+        assert!(ret
+            .text
+            .contains("https://example.com/1 (1,234,567 views, 0 likes)"));
+    }
+
+    #[test]
+    fn render_should_pick_email_image() {
+        let tpl = Tpl::new();
+        let image = |width, format: ImageFormat| Image {
+            role: ImageRole::Thumbnail as i32,
+            url: format!("https://example.com/{}", width),
+            width,
+            format: format as i32,
+            ..Default::default()
+        };
+        let content = Content {
+            images: vec![image(320, ImageFormat::Jpeg), image(640, ImageFormat::Webp)],
+            ..content()
+        };
+        let ret = tpl.render("welcome", &user(None), &[content]).unwrap();
+        // html escaping turns the slashes into &#x2f;
+        assert!(ret.html.contains("example.com&#x2f;320"));
+        assert!(!ret.html.contains("example.com&#x2f;640"));
+        let ret = tpl
+            .render("welcome", &user(None), &[self::content()])
+            .unwrap();
+        assert!(!ret.html.contains("<img"));
+    }
+
+    #[test]
+    fn render_should_show_details() {
+        let tpl = Tpl::new();
+        let movie = Content {
+            name: "Dune".to_string(),
+            details: Some(Details::Movie(MovieDetails {
+                runtime_minutes: 155,
+                rating: "PG-13".to_string(),
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let vlog = Content {
+            name: "Trip".to_string(),
+            details: Some(Details::Vlog(VlogDetails {
+                season: 2,
+                episode: 3,
+                ..Default::default()
+            })),
+            ..Default::default()
+        };
+        let ret = tpl
+            .render("welcome", &user(None), &[movie.clone(), vlog, content()])
+            .unwrap();
+        assert!(ret.text.contains("- Dune (155 min, PG-13)"));
+        assert!(ret.text.contains("- Trip (S2E3)"));
+        assert!(ret.html.contains(">Dune</a> (155 min, PG-13)"));
+        let ret = tpl.render("welcome", &user(Some("zh")), &[movie]).unwrap();
+        assert!(ret.text.contains("- Dune（155 分钟，PG-13）"));
+    }
+
+    #[test]
+    fn render_should_fall_back_by_locale() {
+        let tpl = Tpl::new();
+        let ret = tpl
+            .render("welcome", &user(Some("zh-CN")), &[content()])
+            .unwrap();
+        assert_eq!(ret.subject, "欢迎");
+        assert!(ret.text.contains("123.5万"));
+        // no german variant, the default one is used with german number formatting
+        let ret = tpl
+            .render("welcome", &user(Some("de-DE")), &[content()])
+            .unwrap();
+        assert_eq!(ret.subject, "Welcome");
+        assert!(ret.text.contains("1.234.567 views"));
+    }
+
+    #[test]
+    fn render_should_fail_on_missing_variable() {
+        let mut tpl = Tpl::new();
+        tpl.add("broken", "Hi", "{{ user.nickname }}", "").unwrap();
+        let err = tpl.render("broken", &user(None), &[]).unwrap_err();
+        assert_eq!(err.kind(), minijinja::ErrorKind::UndefinedError);
+        assert!(tpl.render("unknown", &user(None), &[]).is_err());
+        assert_eq!(tpl.names(), vec!["broken", "recall", "remind", "welcome"]);
+    }
+
+    #[test]
+    fn empty_should_only_have_filters() {
+        let mut tpl = Tpl::empty();
+        tpl.add("welcome", "Hi", "", "{{ 1234567 | number }}")
+            .unwrap();
+        let ret = tpl.render("welcome", &user(Some("zh-CN")), &[]).unwrap();
+        assert_eq!((ret.subject.as_str(), ret.text.as_str()), ("Hi", "123.5万"));
+        assert_eq!(tpl.names(), vec!["welcome"]);
+    }
+}
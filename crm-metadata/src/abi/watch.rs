@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgListener, PgPool};
+use tokio::sync::mpsc::Sender;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{CatalogChange, CatalogEntity, ChangeKind, WatchContentRequest},
+    MetaDataService, ServiceResult, WatchStream,
+};
+
+use super::content::{internal, utc_to_ts};
+
+/// notified by the catalog_changes trigger with the new seq
+const CHANNEL: &str = "catalog_changes";
+/// changes read from the log at once when catching up
+const REPLAY_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, sqlx::FromRow)]
+struct ChangeRow {
+    seq: i64,
+    entity: String,
+    entity_id: i32,
+    kind: String,
+    changed_at: DateTime<Utc>,
+}
+
+impl MetaDataService {
+    pub async fn watch_content(&self, req: WatchContentRequest) -> ServiceResult<WatchStream> {
+        // listen before reading the log so no change falls in between
+        let mut listener = PgListener::connect_with(&self.pool)
+            .await
+            .map_err(internal)?;
+        listener.listen(CHANNEL).await.map_err(internal)?;
+        let last_seq = match req.after_seq {
+            Some(seq) => seq as i64,
+            None => sqlx::query_scalar("select coalesce(max(seq), 0) from catalog_changes")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(internal)?,
+        };
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1024);
+        let pool = self.pool.clone();
+        tokio::spawn(async move {
+            if let Err(e) = watch(&pool, listener, last_seq, &tx).await {
+                let _ = tx.send(Err(internal(e))).await;
+            }
+        });
+        Ok(Response::new(Box::pin(ReceiverStream::new(rx))))
+    }
+}
+
+/// notifications only wake the watch up, the changes are always read from the log by seq
+async fn watch(
+    pool: &PgPool,
+    mut listener: PgListener,
+    mut last_seq: i64,
+    tx: &Sender<Result<CatalogChange, Status>>,
+) -> Result<(), sqlx::Error> {
+    loop {
+        loop {
+            let rows = sqlx::query_as::<_, ChangeRow>(
+                r#"select seq, entity::text as entity, entity_id, kind::text as kind, changed_at
+                from catalog_changes where seq > $1 order by seq limit $2"#,
+            )
+            .bind(last_seq)
+            .bind(REPLAY_PAGE_SIZE)
+            .fetch_all(pool)
+            .await?;
+            let n = rows.len() as i64;
+            for row in rows {
+                last_seq = row.seq;
+                if tx.send(Ok(row.into())).await.is_err() {
+                    return Ok(());
+                }
+            }
+            if n < REPLAY_PAGE_SIZE {
+                break;
+            }
+        }
+        tokio::select! {
+            notification = listener.recv() => {
+                notification?;
+            }
+            // the client is gone
+            _ = tx.closed() => return Ok(()),
+        }
+    }
+}
+
+impl From<ChangeRow> for CatalogChange {
+    fn from(row: ChangeRow) -> Self {
+        let entity = match row.entity.as_str() {
+            "content" => CatalogEntity::Content,
+            "publisher" => CatalogEntity::Publisher,
+            _ => CatalogEntity::Unspecified,
+        };
+        let kind = match row.kind.as_str() {
+            "created" => ChangeKind::Created,
+            "updated" => ChangeKind::Updated,
+            "deleted" => ChangeKind::Deleted,
+            _ => ChangeKind::Unspecified,
+        };
+        CatalogChange {
+            seq: row.seq as _,
+            entity: entity as i32,
+            id: row.entity_id as _,
+            kind: kind as i32,
+            changed_at: Some(utc_to_ts(row.changed_at)),
+        }
+    }
+}
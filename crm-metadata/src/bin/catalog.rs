@@ -0,0 +1,66 @@
+use anyhow::{bail, Context, Result};
+use crm_metadata::{
+    pb::{metadata_client::MetadataClient, ExportCatalogRequest},
+    read_catalog, write_catalog, AppConfig,
+};
+use futures::TryStreamExt;
+use tracing::{info, level_filters::LevelFilter, warn};
+use tracing_subscriber::{
+    fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt as _, Layer as _,
+};
+
+// usage: catalog import <file.jsonl|file.csv> | catalog export <file.jsonl|file.csv>
+#[tokio::main]
+async fn main() -> Result<()> {
+    let layer = Layer::new().with_filter(LevelFilter::INFO);
+    tracing_subscriber::registry().with(layer).init();
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let (command, path) = match args.as_slice() {
+        [command, path] => (command.as_str(), path.as_str()),
+        _ => bail!("usage: catalog import|export <file.jsonl|file.csv>"),
+    };
+    let config = AppConfig::try_load().context("load config failed")?;
+    let addr = format!("http://[::1]:{}", config.server.port);
+    let mut client = MetadataClient::connect(addr).await?;
+    match command {
+        "import" => {
+            // lines that don't parse never reach the server, they are merged into its report
+            let mut records = Vec::new();
+            let mut errors = Vec::new();
+            for record in read_catalog(path)? {
+                match record {
+                    Ok(record) => records.push(record),
+                    Err(e) => errors.push(e),
+                }
+            }
+            let report = client
+                .import_catalog(tokio_stream::iter(records))
+                .await?
+                .into_inner();
+            errors.extend(report.errors);
+            errors.sort_by_key(|e| e.line);
+            for e in &errors {
+                warn!("line {}: {}", e.line, e.reason);
+            }
+            info!(
+                "catalog import finished: {} publishers, {} tags, {} contents, {} errors",
+                report.publishers,
+                report.tags,
+                report.contents,
+                errors.len()
+            );
+        }
+        "export" => {
+            let records = client
+                .export_catalog(ExportCatalogRequest {})
+                .await?
+                .into_inner()
+                .try_collect::<Vec<_>>()
+                .await?;
+            write_catalog(path, &records)?;
+            info!("catalog export finished: {} records", records.len());
+        }
+        command => bail!("unknown command {}, expected import or export", command),
+    }
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anyhow::{Context, Result};
+use crm_metadata::{AppConfig, SimilarityJob};
+use tracing::{info, level_filters::LevelFilter};
+use tracing_subscriber::{
+    fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt as _, Layer as _,
+};
+
+// usage: similarity
+#[tokio::main]
+async fn main() -> Result<()> {
+    let layer = Layer::new().with_filter(LevelFilter::INFO);
+    tracing_subscriber::registry().with(layer).init();
+    let config = AppConfig::try_load().context("load config failed")?;
+    let similarity = config
+        .similarity
+        .context("similarity is not configured in metadata.yaml")?;
+    let job = SimilarityJob::try_new(&config.server.db_url, similarity).await?;
+    let report = job.run().await?;
+    info!(
+        "similarity run finished: {} users, {} contents, {} neighbors",
+        report.users, report.contents, report.neighbors
+    );
+    Ok(())
+}
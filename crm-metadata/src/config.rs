@@ -6,6 +6,12 @@ use serde::{Deserialize, Serialize};
 pub struct AppConfig {
     pub server: ServerConfig,
     pub auth: AuthConfig,
+    pub similarity: Option<SimilarityConfig>,
+    pub trending: Option<TrendingConfig>,
+    #[serde(default)]
+    pub catalog: CatalogConfig,
+    #[serde(default)]
+    pub cache: CacheConfig,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -17,6 +23,63 @@ pub struct ServerConfig {
     // pub host: String,
     pub port: u16,
     pub db_url: String,
+    pub user_stats: String,
+    /// prometheus metrics are served on this port when set
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct SimilarityConfig {
+    /// neighbors kept per content
+    pub top_k: u32,
+    /// pairs watched together by fewer users are dropped as noise
+    pub min_users: u32,
+    /// seconds between scheduled runs, 0 disables the background task
+    #[serde(default)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TrendingConfig {
+    /// seconds between refreshes of stale trending values, 0 disables the background task
+    #[serde(default)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, Clone)]
+pub struct CatalogConfig {
+    #[serde(default)]
+    pub backend: CatalogBackend,
+}
+
+/// where Materialize looks contents up
+#[derive(Debug, Default, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogBackend {
+    #[default]
+    Postgres,
+    /// contents generated from their id, the same id always yields the same content.
+    /// only Materialize uses it, the other rpcs still read postgres, connected lazily
+    Fake,
+}
+
+/// the Materialize cache
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct CacheConfig {
+    /// contents kept, 0 disables the cache
+    pub capacity: usize,
+    /// seconds a content is served from the cache before it's looked up again
+    pub ttl: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            capacity: 10_000,
+            ttl: 60,
+        }
+    }
 }
 
 impl AppConfig {
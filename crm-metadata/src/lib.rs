@@ -1,30 +1,96 @@
 mod abi;
 mod config;
 pub mod pb;
+mod similarity;
+mod trending;
 pub use abi::*;
-pub use config::AppConfig;
+use anyhow::Result;
+pub use config::{
+    AppConfig, CacheConfig, CatalogBackend, CatalogConfig, SimilarityConfig, TrendingConfig,
+};
 use futures::Stream;
 use pb::{
     metadata_server::{Metadata, MetadataServer},
-    Content, MaterializeRequest,
+    AddEpisodeRequest, ArchiveContentRequest, CatalogChange, CatalogRecord, Content, ContentEvent,
+    CreateContentRequest, CreatePublisherRequest, CreateSeriesRequest, CreateTagRequest,
+    DeleteContentRequest, DeleteContentResponse, DeletePublisherRequest, DeletePublisherResponse,
+    DeleteTagRequest, DeleteTagResponse, Episode, ExportCatalogRequest, FollowRequest,
+    FollowResponse, GetContentRequest, GetPublisherRequest, ImportCatalogResponse,
+    ListContentsRequest, ListContentsResponse, ListEpisodesRequest, ListEpisodesResponse,
+    ListFollowedRequest, ListFollowedResponse, ListPublishersRequest, ListPublishersResponse,
+    ListTagsRequest, ListTagsResponse, MaterializeRequest, MaterializeResponse,
+    NewFromFollowedRequest, NewFromFollowedResponse, NextUpRequest, NextUpResponse,
+    PublishContentRequest, Publisher, RecommendRequest, RecommendResponse,
+    RecordContentEventResponse, RelatedContentRequest, RelatedContentResponse, Series, Tag,
+    TagAffinityRequest, TagAffinityResponse, UpdateContentRequest, UpdatePublisherRequest,
+    UpdateTagRequest, WatchContentRequest,
 };
-use std::pin::Pin;
-use tonic::{Request, Response, Status, Streaming};
-#[allow(unused)]
+pub use similarity::{SimilarityJob, SimilarityReport};
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::{ops::Deref, pin::Pin, sync::Arc};
+use tonic::{
+    transport::{Channel, Endpoint},
+    Request, Response, Status, Streaming,
+};
+pub use trending::TrendingJob;
+use user_state::pb::user_stats_client::UserStatsClient;
+
+#[derive(Clone)]
 pub struct MetaDataService {
+    inner: Arc<MetaDataServiceInner>,
+}
+#[allow(unused)]
+pub struct MetaDataServiceInner {
     config: AppConfig,
+    pool: PgPool,
+    catalog: Catalog,
+    cache: ContentCache,
+    user_stats: UserStatsClient<Channel>,
 }
 
 impl MetaDataService {
-    pub fn new(config: AppConfig) -> Self {
-        Self { config }
+    pub async fn try_new(config: AppConfig) -> Result<Self> {
+        let pool = match config.catalog.backend {
+            CatalogBackend::Postgres => {
+                let pool = PgPool::connect(&config.server.db_url).await?;
+                sqlx::migrate!().set_ignore_missing(true).run(&pool).await?;
+                pool
+            }
+            // only Materialize is served by the fake catalog, the other rpcs connect on first use
+            CatalogBackend::Fake => PgPoolOptions::new().connect_lazy(&config.server.db_url)?,
+        };
+        // connect lazily so the catalog can serve without user-state being up
+        let channel = Endpoint::from_shared(config.server.user_stats.clone())?.connect_lazy();
+        let user_stats = UserStatsClient::new(channel);
+        let catalog = Catalog::new(config.catalog.backend, pool.clone());
+        let cache = ContentCache::new(&config.cache);
+        let inner = MetaDataServiceInner {
+            config,
+            pool,
+            catalog,
+            cache,
+            user_stats,
+        };
+        Ok(Self {
+            inner: Arc::new(inner),
+        })
     }
     pub fn into_server(self) -> MetadataServer<Self> {
         MetadataServer::new(self)
     }
 }
 
-type ResponseStream = Pin<Box<dyn Stream<Item = Result<Content, Status>> + Send>>;
+impl Deref for MetaDataService {
+    type Target = MetaDataServiceInner;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+type ResponseStream = Pin<Box<dyn Stream<Item = Result<MaterializeResponse, Status>> + Send>>;
+type CatalogStream = Pin<Box<dyn Stream<Item = Result<CatalogRecord, Status>> + Send>>;
+type WatchStream = Pin<Box<dyn Stream<Item = Result<CatalogChange, Status>> + Send>>;
 type ServiceResult<T> = Result<Response<T>, Status>;
 #[tonic::async_trait]
 impl Metadata for MetaDataService {
@@ -36,4 +102,205 @@ impl Metadata for MetaDataService {
         let ret = self.materialize(request.into_inner()).await?;
         Ok(ret)
     }
+
+    async fn create_content(
+        &self,
+        request: Request<CreateContentRequest>,
+    ) -> ServiceResult<Content> {
+        self.create_content(request.into_inner()).await
+    }
+
+    async fn update_content(
+        &self,
+        request: Request<UpdateContentRequest>,
+    ) -> ServiceResult<Content> {
+        self.update_content(request.into_inner()).await
+    }
+
+    async fn delete_content(
+        &self,
+        request: Request<DeleteContentRequest>,
+    ) -> ServiceResult<DeleteContentResponse> {
+        self.delete_content(request.into_inner()).await
+    }
+
+    async fn publish_content(
+        &self,
+        request: Request<PublishContentRequest>,
+    ) -> ServiceResult<Content> {
+        self.publish_content(request.into_inner()).await
+    }
+
+    async fn archive_content(
+        &self,
+        request: Request<ArchiveContentRequest>,
+    ) -> ServiceResult<Content> {
+        self.archive_content(request.into_inner()).await
+    }
+
+    async fn get_content(&self, request: Request<GetContentRequest>) -> ServiceResult<Content> {
+        self.get_content(request.into_inner()).await
+    }
+
+    async fn list_contents(
+        &self,
+        request: Request<ListContentsRequest>,
+    ) -> ServiceResult<ListContentsResponse> {
+        self.list_contents(request.into_inner()).await
+    }
+
+    async fn recommend(
+        &self,
+        request: Request<RecommendRequest>,
+    ) -> ServiceResult<RecommendResponse> {
+        self.recommend(request.into_inner()).await
+    }
+
+    async fn related_content(
+        &self,
+        request: Request<RelatedContentRequest>,
+    ) -> ServiceResult<RelatedContentResponse> {
+        self.related_content(request.into_inner()).await
+    }
+
+    async fn record_content_event(
+        &self,
+        request: Request<Streaming<ContentEvent>>,
+    ) -> ServiceResult<RecordContentEventResponse> {
+        self.record_content_event(request.into_inner()).await
+    }
+
+    async fn create_series(&self, request: Request<CreateSeriesRequest>) -> ServiceResult<Series> {
+        self.create_series(request.into_inner()).await
+    }
+
+    async fn add_episode(&self, request: Request<AddEpisodeRequest>) -> ServiceResult<Episode> {
+        self.add_episode(request.into_inner()).await
+    }
+
+    async fn list_episodes(
+        &self,
+        request: Request<ListEpisodesRequest>,
+    ) -> ServiceResult<ListEpisodesResponse> {
+        self.list_episodes(request.into_inner()).await
+    }
+
+    async fn next_up(&self, request: Request<NextUpRequest>) -> ServiceResult<NextUpResponse> {
+        self.next_up(request.into_inner()).await
+    }
+
+    async fn create_tag(&self, request: Request<CreateTagRequest>) -> ServiceResult<Tag> {
+        self.create_tag(request.into_inner()).await
+    }
+
+    async fn update_tag(&self, request: Request<UpdateTagRequest>) -> ServiceResult<Tag> {
+        self.update_tag(request.into_inner()).await
+    }
+
+    async fn delete_tag(
+        &self,
+        request: Request<DeleteTagRequest>,
+    ) -> ServiceResult<DeleteTagResponse> {
+        self.delete_tag(request.into_inner()).await
+    }
+
+    async fn list_tags(
+        &self,
+        request: Request<ListTagsRequest>,
+    ) -> ServiceResult<ListTagsResponse> {
+        self.list_tags(request.into_inner()).await
+    }
+
+    async fn tag_affinity(
+        &self,
+        request: Request<TagAffinityRequest>,
+    ) -> ServiceResult<TagAffinityResponse> {
+        self.tag_affinity(request.into_inner()).await
+    }
+
+    async fn import_catalog(
+        &self,
+        request: Request<Streaming<CatalogRecord>>,
+    ) -> ServiceResult<ImportCatalogResponse> {
+        self.import_catalog(request.into_inner()).await
+    }
+
+    type ExportCatalogStream = CatalogStream;
+    async fn export_catalog(
+        &self,
+        _request: Request<ExportCatalogRequest>,
+    ) -> ServiceResult<Self::ExportCatalogStream> {
+        self.export_catalog().await
+    }
+
+    async fn create_publisher(
+        &self,
+        request: Request<CreatePublisherRequest>,
+    ) -> ServiceResult<Publisher> {
+        self.create_publisher(request.into_inner()).await
+    }
+
+    async fn get_publisher(
+        &self,
+        request: Request<GetPublisherRequest>,
+    ) -> ServiceResult<Publisher> {
+        self.get_publisher(request.into_inner()).await
+    }
+
+    async fn update_publisher(
+        &self,
+        request: Request<UpdatePublisherRequest>,
+    ) -> ServiceResult<Publisher> {
+        self.update_publisher(request.into_inner()).await
+    }
+
+    async fn delete_publisher(
+        &self,
+        request: Request<DeletePublisherRequest>,
+    ) -> ServiceResult<DeletePublisherResponse> {
+        self.delete_publisher(request.into_inner()).await
+    }
+
+    async fn list_publishers(
+        &self,
+        request: Request<ListPublishersRequest>,
+    ) -> ServiceResult<ListPublishersResponse> {
+        self.list_publishers(request.into_inner()).await
+    }
+
+    async fn follow_publisher(
+        &self,
+        request: Request<FollowRequest>,
+    ) -> ServiceResult<FollowResponse> {
+        self.follow_publisher(request.into_inner()).await
+    }
+
+    async fn unfollow_publisher(
+        &self,
+        request: Request<FollowRequest>,
+    ) -> ServiceResult<FollowResponse> {
+        self.unfollow_publisher(request.into_inner()).await
+    }
+
+    async fn list_followed(
+        &self,
+        request: Request<ListFollowedRequest>,
+    ) -> ServiceResult<ListFollowedResponse> {
+        self.list_followed(request.into_inner()).await
+    }
+
+    async fn new_from_followed(
+        &self,
+        request: Request<NewFromFollowedRequest>,
+    ) -> ServiceResult<NewFromFollowedResponse> {
+        self.new_from_followed(request.into_inner()).await
+    }
+
+    type WatchContentStream = WatchStream;
+    async fn watch_content(
+        &self,
+        request: Request<WatchContentRequest>,
+    ) -> ServiceResult<Self::WatchContentStream> {
+        self.watch_content(request.into_inner()).await
+    }
 }
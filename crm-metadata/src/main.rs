@@ -1,5 +1,8 @@
+use std::net::SocketAddr;
+
 use anyhow::{Context, Result};
-use crm_metadata::AppConfig;
+use crm_metadata::{AppConfig, SimilarityJob, TrendingJob};
+use metrics_exporter_prometheus::PrometheusBuilder;
 use tonic::transport::Server;
 use tracing::{info, level_filters::LevelFilter};
 use tracing_subscriber::{
@@ -12,7 +15,29 @@ async fn main() -> Result<()> {
     tracing_subscriber::registry().with(layer).init();
     let config = AppConfig::try_load().context("load config failed")?;
     let addr = format!("[::1]:{}", &config.server.port).parse().unwrap();
-    let svc = crm_metadata::MetaDataService::new(config).into_server();
+    if let Some(port) = config.server.metrics_port {
+        let metrics_addr: SocketAddr = format!("[::1]:{}", port).parse()?;
+        PrometheusBuilder::new()
+            .with_http_listener(metrics_addr)
+            .install()
+            .context("install metrics exporter failed")?;
+        info!("Metrics exported on {}", metrics_addr);
+    }
+    if let Some(similarity) = config.similarity.clone().filter(|s| s.interval > 0) {
+        info!("Similarity job scheduled every {}s", similarity.interval);
+        SimilarityJob::try_new(&config.server.db_url, similarity)
+            .await?
+            .schedule();
+    }
+    let trending = config.trending.clone().filter(|t| t.interval > 0);
+    let db_url = config.server.db_url.clone();
+    let svc = crm_metadata::MetaDataService::try_new(config)
+        .await?
+        .into_server();
+    if let Some(trending) = trending {
+        info!("Trending job scheduled every {}s", trending.interval);
+        TrendingJob::try_new(&db_url, trending).await?.schedule();
+    }
     info!("MetadataService listening on {}", addr);
     Server::builder().add_service(svc).serve(addr).await?;
     Ok(())
@@ -13,8 +13,6 @@ pub struct Content {
     pub publishers: ::prost::alloc::vec::Vec<Publisher>,
     #[prost(string, tag = "5")]
     pub url: ::prost::alloc::string::String,
-    #[prost(string, tag = "6")]
-    pub images: ::prost::alloc::string::String,
     #[prost(enumeration = "ContentType", tag = "7")]
     pub content_type: i32,
     #[prost(message, optional, tag = "8")]
@@ -25,6 +23,99 @@ pub struct Content {
     pub likes: u64,
     #[prost(uint64, tag = "11")]
     pub dislikes: u64,
+    /// wilson lower bound of likes over likes and dislikes
+    #[prost(double, tag = "12")]
+    pub rating: f64,
+    /// time decayed views of the last 14 days
+    #[prost(double, tag = "13")]
+    pub trending: f64,
+    #[prost(enumeration = "ContentStatus", tag = "14")]
+    pub status: i32,
+    /// available from, immediately when unset
+    #[prost(message, optional, tag = "15")]
+    pub publish_at: ::core::option::Option<::prost_types::Timestamp>,
+    /// available until, forever when unset
+    #[prost(message, optional, tag = "16")]
+    pub expire_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, repeated, tag = "20")]
+    pub tags: ::prost::alloc::vec::Vec<Tag>,
+    /// variants by role and size, the roles required by content_type are always there
+    #[prost(message, repeated, tag = "21")]
+    pub images: ::prost::alloc::vec::Vec<Image>,
+    /// must match content_type, unset when there is nothing specific to say
+    #[prost(oneof = "content::Details", tags = "17, 18, 19")]
+    pub details: ::core::option::Option<content::Details>,
+}
+/// Nested message and enum types in `Content`.
+pub mod content {
+    /// must match content_type, unset when there is nothing specific to say
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Details {
+        #[prost(message, tag = "17")]
+        Vlog(super::VlogDetails),
+        #[prost(message, tag = "18")]
+        Movie(super::MovieDetails),
+        #[prost(message, tag = "19")]
+        AiGenerated(super::AiGeneratedDetails),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Image {
+    #[prost(enumeration = "ImageRole", tag = "1")]
+    pub role: i32,
+    #[prost(string, tag = "2")]
+    pub url: ::prost::alloc::string::String,
+    /// pixels, 0 when unknown
+    #[prost(uint32, tag = "3")]
+    pub width: u32,
+    #[prost(uint32, tag = "4")]
+    pub height: u32,
+    #[prost(enumeration = "ImageFormat", tag = "5")]
+    pub format: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tag {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "TagKind", tag = "3")]
+    pub kind: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
+pub struct VlogDetails {
+    #[prost(uint32, tag = "1")]
+    pub duration_secs: u32,
+    /// 0 when the vlog is not part of a season
+    #[prost(uint32, tag = "2")]
+    pub season: u32,
+    #[prost(uint32, tag = "3")]
+    pub episode: u32,
+    #[prost(string, tag = "4")]
+    pub episode_title: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
+pub struct MovieDetails {
+    #[prost(uint32, tag = "1")]
+    pub runtime_minutes: u32,
+    /// age rating, e.g. PG-13
+    #[prost(string, tag = "2")]
+    pub rating: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "3")]
+    pub cast: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, ::prost::Message)]
+pub struct AiGeneratedDetails {
+    /// generator model and version, e.g. sora-1.0
+    #[prost(string, tag = "1")]
+    pub model: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub prompt: ::prost::alloc::string::String,
+    /// where the prompt or source material came from
+    #[prost(string, tag = "3")]
+    pub provenance: ::prost::alloc::string::String,
 }
 #[derive(derive_builder::Builder)]
 #[builder(setter(into, strip_option), default)]
@@ -41,6 +132,516 @@ pub struct Publisher {
 pub struct MaterializeRequest {
     #[prost(uint32, tag = "1")]
     pub id: u32,
+    /// also return content that is not available, e.g. to preview drafts
+    #[prost(bool, tag = "2")]
+    pub include_unavailable: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MaterializeError {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MaterializeResponse {
+    #[prost(oneof = "materialize_response::Result", tags = "1, 2")]
+    pub result: ::core::option::Option<materialize_response::Result>,
+}
+/// Nested message and enum types in `MaterializeResponse`.
+pub mod materialize_response {
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Result {
+        #[prost(message, tag = "1")]
+        Content(super::Content),
+        #[prost(message, tag = "2")]
+        Error(super::MaterializeError),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateContentRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(uint32, repeated, tag = "3")]
+    pub publisher_ids: ::prost::alloc::vec::Vec<u32>,
+    #[prost(string, tag = "4")]
+    pub url: ::prost::alloc::string::String,
+    #[prost(enumeration = "ContentType", tag = "6")]
+    pub content_type: i32,
+    /// unspecified creates a draft, set published to go live right away
+    #[prost(enumeration = "ContentStatus", tag = "7")]
+    pub status: i32,
+    #[prost(message, optional, tag = "8")]
+    pub publish_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "9")]
+    pub expire_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint32, repeated, tag = "13")]
+    pub tag_ids: ::prost::alloc::vec::Vec<u32>,
+    #[prost(message, repeated, tag = "14")]
+    pub images: ::prost::alloc::vec::Vec<Image>,
+    #[prost(oneof = "create_content_request::Details", tags = "10, 11, 12")]
+    pub details: ::core::option::Option<create_content_request::Details>,
+}
+/// Nested message and enum types in `CreateContentRequest`.
+pub mod create_content_request {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Details {
+        #[prost(message, tag = "10")]
+        Vlog(super::VlogDetails),
+        #[prost(message, tag = "11")]
+        Movie(super::MovieDetails),
+        #[prost(message, tag = "12")]
+        AiGenerated(super::AiGeneratedDetails),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(uint32, repeated, tag = "4")]
+    pub publisher_ids: ::prost::alloc::vec::Vec<u32>,
+    #[prost(string, tag = "5")]
+    pub url: ::prost::alloc::string::String,
+    #[prost(enumeration = "ContentType", tag = "7")]
+    pub content_type: i32,
+    /// replaces the assigned tags
+    #[prost(uint32, repeated, tag = "11")]
+    pub tag_ids: ::prost::alloc::vec::Vec<u32>,
+    #[prost(message, repeated, tag = "12")]
+    pub images: ::prost::alloc::vec::Vec<Image>,
+    /// replaces the stored details, unset clears them
+    #[prost(oneof = "update_content_request::Details", tags = "8, 9, 10")]
+    pub details: ::core::option::Option<update_content_request::Details>,
+}
+/// Nested message and enum types in `UpdateContentRequest`.
+pub mod update_content_request {
+    /// replaces the stored details, unset clears them
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Details {
+        #[prost(message, tag = "8")]
+        Vlog(super::VlogDetails),
+        #[prost(message, tag = "9")]
+        Movie(super::MovieDetails),
+        #[prost(message, tag = "10")]
+        AiGenerated(super::AiGeneratedDetails),
+    }
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteContentResponse {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+/// from draft, or to move the window of published content
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PublishContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(message, optional, tag = "2")]
+    pub publish_at: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub expire_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+/// retires draft or published content, archived content can't be published again
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ArchiveContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreatePublisherRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub avatar: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListContentsRequest {
+    #[prost(enumeration = "ContentType", optional, tag = "1")]
+    pub content_type: ::core::option::Option<i32>,
+    #[prost(message, optional, tag = "2")]
+    pub created_after: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(message, optional, tag = "3")]
+    pub created_before: ::core::option::Option<::prost_types::Timestamp>,
+    #[prost(uint32, optional, tag = "4")]
+    pub publisher_id: ::core::option::Option<u32>,
+    /// case insensitive substring of the content name
+    #[prost(string, tag = "5")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "ContentSort", tag = "6")]
+    pub sort: i32,
+    /// defaults to 20, at most 100
+    #[prost(uint32, tag = "7")]
+    pub page_size: u32,
+    /// next_cursor of the previous page, must be used with the same sort
+    #[prost(string, tag = "8")]
+    pub cursor: ::prost::alloc::string::String,
+    /// also list drafts, archived and out of window content
+    #[prost(bool, tag = "9")]
+    pub include_unavailable: bool,
+    /// content having all of the tags
+    #[prost(uint32, repeated, tag = "10")]
+    pub tag_ids: ::prost::alloc::vec::Vec<u32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListContentsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub contents: ::prost::alloc::vec::Vec<Content>,
+    /// empty when there are no more pages
+    #[prost(string, tag = "2")]
+    pub next_cursor: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    /// defaults to 10, at most 100
+    #[prost(uint32, tag = "2")]
+    pub n: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecommendResponse {
+    /// best match first
+    #[prost(message, repeated, tag = "1")]
+    pub contents: ::prost::alloc::vec::Vec<Content>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RelatedContentRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    /// defaults to 10, at most 100
+    #[prost(uint32, tag = "2")]
+    pub n: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelatedContent {
+    #[prost(message, optional, tag = "1")]
+    pub content: ::core::option::Option<Content>,
+    /// jaccard similarity of the users that watched both
+    #[prost(double, tag = "2")]
+    pub score: f64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RelatedContentResponse {
+    /// most similar first
+    #[prost(message, repeated, tag = "1")]
+    pub related: ::prost::alloc::vec::Vec<RelatedContent>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ContentEvent {
+    #[prost(uint32, tag = "1")]
+    pub content_id: u32,
+    #[prost(enumeration = "ContentEventType", tag = "2")]
+    pub event_type: i32,
+    /// defaults to the time it is received
+    #[prost(message, optional, tag = "3")]
+    pub occurred_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct RecordContentEventResponse {
+    #[prost(uint64, tag = "1")]
+    pub accepted: u64,
+    /// unknown content ids and unspecified event types
+    #[prost(uint64, tag = "2")]
+    pub rejected: u64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Series {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "4")]
+    pub created_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Episode {
+    #[prost(uint32, tag = "1")]
+    pub series_id: u32,
+    #[prost(uint32, tag = "2")]
+    pub season: u32,
+    #[prost(uint32, tag = "3")]
+    pub episode: u32,
+    #[prost(message, optional, tag = "4")]
+    pub content: ::core::option::Option<Content>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateSeriesRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub description: ::prost::alloc::string::String,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AddEpisodeRequest {
+    #[prost(uint32, tag = "1")]
+    pub series_id: u32,
+    #[prost(uint32, tag = "2")]
+    pub season: u32,
+    #[prost(uint32, tag = "3")]
+    pub episode: u32,
+    #[prost(uint32, tag = "4")]
+    pub content_id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListEpisodesRequest {
+    #[prost(uint32, tag = "1")]
+    pub series_id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListEpisodesResponse {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<Series>,
+    /// by season then episode
+    #[prost(message, repeated, tag = "2")]
+    pub episodes: ::prost::alloc::vec::Vec<Episode>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NextUpRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NextUp {
+    #[prost(message, optional, tag = "1")]
+    pub series: ::core::option::Option<Series>,
+    #[prost(message, optional, tag = "2")]
+    pub episode: ::core::option::Option<Episode>,
+    /// the episode was started but not finished
+    #[prost(bool, tag = "3")]
+    pub resume: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NextUpResponse {
+    /// one per series the user has started, series watched to the end are left out
+    #[prost(message, repeated, tag = "1")]
+    pub next_up: ::prost::alloc::vec::Vec<NextUp>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTagRequest {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "TagKind", tag = "2")]
+    pub kind: i32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateTagRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(enumeration = "TagKind", tag = "3")]
+    pub kind: i32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteTagRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeleteTagResponse {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListTagsRequest {
+    /// all kinds when unset
+    #[prost(enumeration = "TagKind", optional, tag = "1")]
+    pub kind: ::core::option::Option<i32>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListTagsResponse {
+    /// by name
+    #[prost(message, repeated, tag = "1")]
+    pub tags: ::prost::alloc::vec::Vec<Tag>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagAffinityRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    /// defaults to 10, at most 100
+    #[prost(uint32, tag = "2")]
+    pub n: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagAffinity {
+    #[prost(message, optional, tag = "1")]
+    pub tag: ::core::option::Option<Tag>,
+    /// weighted share of the watched contents with the tag, in \[0, 1\]
+    #[prost(double, tag = "2")]
+    pub score: f64,
+    /// watched contents with the tag
+    #[prost(uint32, tag = "3")]
+    pub contents: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TagAffinityResponse {
+    /// highest score first
+    #[prost(message, repeated, tag = "1")]
+    pub affinities: ::prost::alloc::vec::Vec<TagAffinity>,
+}
+/// one line of a catalog file, publishers and tags come before the contents referring to them
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CatalogRecord {
+    /// line in the source file, echoed in the import errors
+    #[prost(uint32, tag = "1")]
+    pub line: u32,
+    /// upserted by id, a new one is created for id 0
+    #[prost(oneof = "catalog_record::Record", tags = "2, 3, 4")]
+    pub record: ::core::option::Option<catalog_record::Record>,
+}
+/// Nested message and enum types in `CatalogRecord`.
+pub mod catalog_record {
+    /// upserted by id, a new one is created for id 0
+    #[allow(clippy::large_enum_variant)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Record {
+        #[prost(message, tag = "2")]
+        Publisher(super::Publisher),
+        /// publishers and tags are referred to by id, the counters are left alone
+        #[prost(message, tag = "3")]
+        Content(super::Content),
+        #[prost(message, tag = "4")]
+        Tag(super::Tag),
+    }
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportError {
+    #[prost(uint32, tag = "1")]
+    pub line: u32,
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportCatalogResponse {
+    #[prost(uint32, tag = "1")]
+    pub publishers: u32,
+    #[prost(uint32, tag = "2")]
+    pub contents: u32,
+    /// records that failed validation or couldn't be stored, the others are imported
+    #[prost(message, repeated, tag = "3")]
+    pub errors: ::prost::alloc::vec::Vec<ImportError>,
+    #[prost(uint32, tag = "4")]
+    pub tags: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ExportCatalogRequest {}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct WatchContentRequest {
+    /// resume after the last seen change, unset to only watch changes from now on
+    #[prost(uint64, optional, tag = "1")]
+    pub after_seq: ::core::option::Option<u64>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CatalogChange {
+    /// increases with every change, in commit order
+    #[prost(uint64, tag = "1")]
+    pub seq: u64,
+    #[prost(enumeration = "CatalogEntity", tag = "2")]
+    pub entity: i32,
+    #[prost(uint32, tag = "3")]
+    pub id: u32,
+    #[prost(enumeration = "ChangeKind", tag = "4")]
+    pub kind: i32,
+    #[prost(message, optional, tag = "5")]
+    pub changed_at: ::core::option::Option<::prost_types::Timestamp>,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetPublisherRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdatePublisherRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+    #[prost(string, tag = "2")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub avatar: ::prost::alloc::string::String,
+}
+/// the publisher is unlinked from its contents and dropped from the follow lists
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeletePublisherRequest {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct DeletePublisherResponse {
+    #[prost(uint32, tag = "1")]
+    pub id: u32,
+}
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct ListPublishersRequest {
+    /// publishers with a greater id, for paging
+    #[prost(uint32, tag = "1")]
+    pub after_id: u32,
+    /// defaults to 10, at most 100
+    #[prost(uint32, tag = "2")]
+    pub n: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPublishersResponse {
+    /// by id
+    #[prost(message, repeated, tag = "1")]
+    pub publishers: ::prost::alloc::vec::Vec<Publisher>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FollowRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub publisher_id: u32,
+}
+/// following twice or unfollowing a publisher not followed is fine
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct FollowResponse {}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFollowedRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFollowedResponse {
+    /// by name
+    #[prost(message, repeated, tag = "1")]
+    pub publishers: ::prost::alloc::vec::Vec<Publisher>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NewFromFollowedRequest {
+    #[prost(string, tag = "1")]
+    pub email: ::prost::alloc::string::String,
+    /// contents released after this, i.e. created or published when that was scheduled later,
+    /// all of them when unset
+    #[prost(message, optional, tag = "2")]
+    pub since: ::core::option::Option<::prost_types::Timestamp>,
+    /// defaults to 10, at most 100
+    #[prost(uint32, tag = "3")]
+    pub n: u32,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NewFromFollowedResponse {
+    /// available contents of any followed publisher, newest first
+    #[prost(message, repeated, tag = "1")]
+    pub contents: ::prost::alloc::vec::Vec<Content>,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -82,45 +683,303 @@ impl ContentType {
         }
     }
 }
-/// Generated client implementations.
-pub mod metadata_client {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value
-    )]
-    use tonic::codegen::http::Uri;
-    use tonic::codegen::*;
-    #[derive(Debug, Clone)]
-    pub struct MetadataClient<T> {
-        inner: tonic::client::Grpc<T>,
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ImageRole {
+    Unspecified = 0,
+    Poster = 1,
+    Thumbnail = 2,
+    Banner = 3,
+}
+impl ImageRole {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "IMAGE_ROLE_UNSPECIFIED",
+            Self::Poster => "IMAGE_ROLE_POSTER",
+            Self::Thumbnail => "IMAGE_ROLE_THUMBNAIL",
+            Self::Banner => "IMAGE_ROLE_BANNER",
+        }
     }
-    impl MetadataClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "IMAGE_ROLE_UNSPECIFIED" => Some(Self::Unspecified),
+            "IMAGE_ROLE_POSTER" => Some(Self::Poster),
+            "IMAGE_ROLE_THUMBNAIL" => Some(Self::Thumbnail),
+            "IMAGE_ROLE_BANNER" => Some(Self::Banner),
+            _ => None,
         }
     }
-    impl<T> MetadataClient<T>
-    where
-        T: tonic::client::GrpcService<tonic::body::BoxBody>,
-        T::Error: Into<StdError>,
-        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
-        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
-    {
-        pub fn new(inner: T) -> Self {
-            let inner = tonic::client::Grpc::new(inner);
-            Self { inner }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ImageFormat {
+    Unspecified = 0,
+    Jpeg = 1,
+    Png = 2,
+    Webp = 3,
+    Avif = 4,
+}
+impl ImageFormat {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "IMAGE_FORMAT_UNSPECIFIED",
+            Self::Jpeg => "IMAGE_FORMAT_JPEG",
+            Self::Png => "IMAGE_FORMAT_PNG",
+            Self::Webp => "IMAGE_FORMAT_WEBP",
+            Self::Avif => "IMAGE_FORMAT_AVIF",
         }
-        pub fn with_origin(inner: T, origin: Uri) -> Self {
-            let inner = tonic::client::Grpc::with_origin(inner, origin);
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "IMAGE_FORMAT_UNSPECIFIED" => Some(Self::Unspecified),
+            "IMAGE_FORMAT_JPEG" => Some(Self::Jpeg),
+            "IMAGE_FORMAT_PNG" => Some(Self::Png),
+            "IMAGE_FORMAT_WEBP" => Some(Self::Webp),
+            "IMAGE_FORMAT_AVIF" => Some(Self::Avif),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TagKind {
+    /// created as a plain tag
+    Unspecified = 0,
+    Tag = 1,
+    Genre = 2,
+}
+impl TagKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "TAG_KIND_UNSPECIFIED",
+            Self::Tag => "TAG_KIND_TAG",
+            Self::Genre => "TAG_KIND_GENRE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "TAG_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "TAG_KIND_TAG" => Some(Self::Tag),
+            "TAG_KIND_GENRE" => Some(Self::Genre),
+            _ => None,
+        }
+    }
+}
+/// only published content inside its publish_at/expire_at window is available
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ContentStatus {
+    Unspecified = 0,
+    Draft = 1,
+    Published = 2,
+    Archived = 3,
+}
+impl ContentStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CONTENT_STATUS_UNSPECIFIED",
+            Self::Draft => "CONTENT_STATUS_DRAFT",
+            Self::Published => "CONTENT_STATUS_PUBLISHED",
+            Self::Archived => "CONTENT_STATUS_ARCHIVED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CONTENT_STATUS_UNSPECIFIED" => Some(Self::Unspecified),
+            "CONTENT_STATUS_DRAFT" => Some(Self::Draft),
+            "CONTENT_STATUS_PUBLISHED" => Some(Self::Published),
+            "CONTENT_STATUS_ARCHIVED" => Some(Self::Archived),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ContentEventType {
+    Unspecified = 0,
+    View = 1,
+    Like = 2,
+    Dislike = 3,
+}
+impl ContentEventType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CONTENT_EVENT_TYPE_UNSPECIFIED",
+            Self::View => "CONTENT_EVENT_TYPE_VIEW",
+            Self::Like => "CONTENT_EVENT_TYPE_LIKE",
+            Self::Dislike => "CONTENT_EVENT_TYPE_DISLIKE",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CONTENT_EVENT_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+            "CONTENT_EVENT_TYPE_VIEW" => Some(Self::View),
+            "CONTENT_EVENT_TYPE_LIKE" => Some(Self::Like),
+            "CONTENT_EVENT_TYPE_DISLIKE" => Some(Self::Dislike),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ContentSort {
+    Recent = 0,
+    Views = 1,
+    Likes = 2,
+    Rating = 3,
+    Trending = 4,
+}
+impl ContentSort {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Recent => "CONTENT_SORT_RECENT",
+            Self::Views => "CONTENT_SORT_VIEWS",
+            Self::Likes => "CONTENT_SORT_LIKES",
+            Self::Rating => "CONTENT_SORT_RATING",
+            Self::Trending => "CONTENT_SORT_TRENDING",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CONTENT_SORT_RECENT" => Some(Self::Recent),
+            "CONTENT_SORT_VIEWS" => Some(Self::Views),
+            "CONTENT_SORT_LIKES" => Some(Self::Likes),
+            "CONTENT_SORT_RATING" => Some(Self::Rating),
+            "CONTENT_SORT_TRENDING" => Some(Self::Trending),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum CatalogEntity {
+    Unspecified = 0,
+    Content = 1,
+    Publisher = 2,
+}
+impl CatalogEntity {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CATALOG_ENTITY_UNSPECIFIED",
+            Self::Content => "CATALOG_ENTITY_CONTENT",
+            Self::Publisher => "CATALOG_ENTITY_PUBLISHER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CATALOG_ENTITY_UNSPECIFIED" => Some(Self::Unspecified),
+            "CATALOG_ENTITY_CONTENT" => Some(Self::Content),
+            "CATALOG_ENTITY_PUBLISHER" => Some(Self::Publisher),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChangeKind {
+    Unspecified = 0,
+    Created = 1,
+    Updated = 2,
+    Deleted = 3,
+}
+impl ChangeKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Self::Unspecified => "CHANGE_KIND_UNSPECIFIED",
+            Self::Created => "CHANGE_KIND_CREATED",
+            Self::Updated => "CHANGE_KIND_UPDATED",
+            Self::Deleted => "CHANGE_KIND_DELETED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "CHANGE_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+            "CHANGE_KIND_CREATED" => Some(Self::Created),
+            "CHANGE_KIND_UPDATED" => Some(Self::Updated),
+            "CHANGE_KIND_DELETED" => Some(Self::Deleted),
+            _ => None,
+        }
+    }
+}
+/// Generated client implementations.
+pub mod metadata_client {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value
+    )]
+    use tonic::codegen::http::Uri;
+    use tonic::codegen::*;
+    #[derive(Debug, Clone)]
+    pub struct MetadataClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl MetadataClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> MetadataClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + std::marker::Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + std::marker::Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
             Self { inner }
         }
         pub fn with_interceptor<F>(
@@ -176,7 +1035,7 @@ pub mod metadata_client {
             &mut self,
             request: impl tonic::IntoStreamingRequest<Message = super::MaterializeRequest>,
         ) -> std::result::Result<
-            tonic::Response<tonic::codec::Streaming<super::Content>>,
+            tonic::Response<tonic::codec::Streaming<super::MaterializeResponse>>,
             tonic::Status,
         > {
             self.inner.ready().await.map_err(|e| {
@@ -189,119 +1048,1953 @@ pub mod metadata_client {
                 .insert(GrpcMethod::new("crm_metadata.Metadata", "Materialize"));
             self.inner.streaming(req, path, codec).await
         }
-    }
-}
-/// Generated server implementations.
-pub mod metadata_server {
-    #![allow(
-        unused_variables,
-        dead_code,
-        missing_docs,
-        clippy::wildcard_imports,
-        clippy::let_unit_value
-    )]
-    use tonic::codegen::*;
-    /// Generated trait containing gRPC methods that should be implemented for use with MetadataServer.
-    #[async_trait]
-    pub trait Metadata: std::marker::Send + std::marker::Sync + 'static {
-        /// Server streaming response type for the Materialize method.
-        type MaterializeStream: tonic::codegen::tokio_stream::Stream<
-                Item = std::result::Result<super::Content, tonic::Status>,
-            > + std::marker::Send
-            + 'static;
-        async fn materialize(
-            &self,
-            request: tonic::Request<tonic::Streaming<super::MaterializeRequest>>,
-        ) -> std::result::Result<tonic::Response<Self::MaterializeStream>, tonic::Status>;
-    }
-    #[derive(Debug)]
-    pub struct MetadataServer<T> {
-        inner: Arc<T>,
-        accept_compression_encodings: EnabledCompressionEncodings,
-        send_compression_encodings: EnabledCompressionEncodings,
-        max_decoding_message_size: Option<usize>,
-        max_encoding_message_size: Option<usize>,
-    }
-    impl<T> MetadataServer<T> {
-        pub fn new(inner: T) -> Self {
-            Self::from_arc(Arc::new(inner))
+        pub async fn create_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/CreateContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "CreateContent"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn from_arc(inner: Arc<T>) -> Self {
-            Self {
-                inner,
-                accept_compression_encodings: Default::default(),
-                send_compression_encodings: Default::default(),
-                max_decoding_message_size: None,
-                max_encoding_message_size: None,
-            }
+        pub async fn update_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/UpdateContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "UpdateContent"));
+            self.inner.unary(req, path, codec).await
         }
-        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
-        where
-            F: tonic::service::Interceptor,
+        pub async fn delete_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeleteContentResponse>, tonic::Status>
         {
-            InterceptedService::new(Self::new(inner), interceptor)
-        }
-        /// Enable decompressing requests with the given encoding.
-        #[must_use]
-        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.accept_compression_encodings.enable(encoding);
-            self
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/DeleteContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "DeleteContent"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Compress responses with the given encoding, if the client supports it.
-        #[must_use]
-        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
-            self.send_compression_encodings.enable(encoding);
-            self
+        pub async fn publish_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PublishContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/PublishContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "PublishContent"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of a decoded message.
-        ///
-        /// Default: `4MB`
-        #[must_use]
-        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
-            self.max_decoding_message_size = Some(limit);
-            self
+        pub async fn archive_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ArchiveContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ArchiveContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ArchiveContent"));
+            self.inner.unary(req, path, codec).await
         }
-        /// Limits the maximum size of an encoded message.
-        ///
-        /// Default: `usize::MAX`
-        #[must_use]
-        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
-            self.max_encoding_message_size = Some(limit);
-            self
+        pub async fn get_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/GetContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "GetContent"));
+            self.inner.unary(req, path, codec).await
         }
-    }
-    impl<T, B> tonic::codegen::Service<http::Request<B>> for MetadataServer<T>
-    where
-        T: Metadata,
-        B: Body + std::marker::Send + 'static,
-        B::Error: Into<StdError> + std::marker::Send + 'static,
-    {
-        type Response = http::Response<tonic::body::BoxBody>;
-        type Error = std::convert::Infallible;
-        type Future = BoxFuture<Self::Response, Self::Error>;
-        fn poll_ready(
+        pub async fn list_contents(
             &mut self,
-            _cx: &mut Context<'_>,
-        ) -> Poll<std::result::Result<(), Self::Error>> {
-            Poll::Ready(Ok(()))
+            request: impl tonic::IntoRequest<super::ListContentsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListContentsResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ListContents");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ListContents"));
+            self.inner.unary(req, path, codec).await
         }
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            match req.uri().path() {
-                "/crm_metadata.Metadata/Materialize" => {
+        pub async fn recommend(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RecommendRequest>,
+        ) -> std::result::Result<tonic::Response<super::RecommendResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/Recommend");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "Recommend"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn related_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::RelatedContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::RelatedContentResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/RelatedContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "RelatedContent"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn record_content_event(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::ContentEvent>,
+        ) -> std::result::Result<tonic::Response<super::RecordContentEventResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/RecordContentEvent");
+            let mut req = request.into_streaming_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "crm_metadata.Metadata",
+                "RecordContentEvent",
+            ));
+            self.inner.client_streaming(req, path, codec).await
+        }
+        pub async fn create_series(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::Series>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/CreateSeries");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "CreateSeries"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn add_episode(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AddEpisodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Episode>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/AddEpisode");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "AddEpisode"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_episodes(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListEpisodesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListEpisodesResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ListEpisodes");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ListEpisodes"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn next_up(
+            &mut self,
+            request: impl tonic::IntoRequest<super::NextUpRequest>,
+        ) -> std::result::Result<tonic::Response<super::NextUpResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/NextUp");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "NextUp"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn create_tag(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreateTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::Tag>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/CreateTag");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "CreateTag"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_tag(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::Tag>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/UpdateTag");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "UpdateTag"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_tag(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeleteTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeleteTagResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/DeleteTag");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "DeleteTag"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_tags(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListTagsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListTagsResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ListTags");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ListTags"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn tag_affinity(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TagAffinityRequest>,
+        ) -> std::result::Result<tonic::Response<super::TagAffinityResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/TagAffinity");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "TagAffinity"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn import_catalog(
+            &mut self,
+            request: impl tonic::IntoStreamingRequest<Message = super::CatalogRecord>,
+        ) -> std::result::Result<tonic::Response<super::ImportCatalogResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ImportCatalog");
+            let mut req = request.into_streaming_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ImportCatalog"));
+            self.inner.client_streaming(req, path, codec).await
+        }
+        /// all publishers, then all tags, then all contents, available or not
+        pub async fn export_catalog(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExportCatalogRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::CatalogRecord>>,
+            tonic::Status,
+        > {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ExportCatalog");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ExportCatalog"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn create_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CreatePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/CreatePublisher");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "CreatePublisher"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn get_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/GetPublisher");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "GetPublisher"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdatePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/UpdatePublisher");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "UpdatePublisher"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn delete_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DeletePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeletePublisherResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/DeletePublisher");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "DeletePublisher"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_publishers(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListPublishersRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListPublishersResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ListPublishers");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ListPublishers"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn follow_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FollowRequest>,
+        ) -> std::result::Result<tonic::Response<super::FollowResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/FollowPublisher");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "FollowPublisher"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn unfollow_publisher(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FollowRequest>,
+        ) -> std::result::Result<tonic::Response<super::FollowResponse>, tonic::Status> {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/UnfollowPublisher");
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new(
+                "crm_metadata.Metadata",
+                "UnfollowPublisher",
+            ));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn list_followed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListFollowedRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListFollowedResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/ListFollowed");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "ListFollowed"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn new_from_followed(
+            &mut self,
+            request: impl tonic::IntoRequest<super::NewFromFollowedRequest>,
+        ) -> std::result::Result<tonic::Response<super::NewFromFollowedResponse>, tonic::Status>
+        {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path =
+                http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/NewFromFollowed");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "NewFromFollowed"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// changes to contents and publishers, counters excluded
+        pub async fn watch_content(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchContentRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::CatalogChange>>,
+            tonic::Status,
+        > {
+            self.inner.ready().await.map_err(|e| {
+                tonic::Status::unknown(format!("Service was not ready: {}", e.into()))
+            })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static("/crm_metadata.Metadata/WatchContent");
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("crm_metadata.Metadata", "WatchContent"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod metadata_server {
+    #![allow(
+        unused_variables,
+        dead_code,
+        missing_docs,
+        clippy::wildcard_imports,
+        clippy::let_unit_value
+    )]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with MetadataServer.
+    #[async_trait]
+    pub trait Metadata: std::marker::Send + std::marker::Sync + 'static {
+        /// Server streaming response type for the Materialize method.
+        type MaterializeStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::MaterializeResponse, tonic::Status>,
+            > + std::marker::Send
+            + 'static;
+        async fn materialize(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::MaterializeRequest>>,
+        ) -> std::result::Result<tonic::Response<Self::MaterializeStream>, tonic::Status>;
+        async fn create_content(
+            &self,
+            request: tonic::Request<super::CreateContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status>;
+        async fn update_content(
+            &self,
+            request: tonic::Request<super::UpdateContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status>;
+        async fn delete_content(
+            &self,
+            request: tonic::Request<super::DeleteContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeleteContentResponse>, tonic::Status>;
+        async fn publish_content(
+            &self,
+            request: tonic::Request<super::PublishContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status>;
+        async fn archive_content(
+            &self,
+            request: tonic::Request<super::ArchiveContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status>;
+        async fn get_content(
+            &self,
+            request: tonic::Request<super::GetContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::Content>, tonic::Status>;
+        async fn list_contents(
+            &self,
+            request: tonic::Request<super::ListContentsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListContentsResponse>, tonic::Status>;
+        async fn recommend(
+            &self,
+            request: tonic::Request<super::RecommendRequest>,
+        ) -> std::result::Result<tonic::Response<super::RecommendResponse>, tonic::Status>;
+        async fn related_content(
+            &self,
+            request: tonic::Request<super::RelatedContentRequest>,
+        ) -> std::result::Result<tonic::Response<super::RelatedContentResponse>, tonic::Status>;
+        async fn record_content_event(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::ContentEvent>>,
+        ) -> std::result::Result<tonic::Response<super::RecordContentEventResponse>, tonic::Status>;
+        async fn create_series(
+            &self,
+            request: tonic::Request<super::CreateSeriesRequest>,
+        ) -> std::result::Result<tonic::Response<super::Series>, tonic::Status>;
+        async fn add_episode(
+            &self,
+            request: tonic::Request<super::AddEpisodeRequest>,
+        ) -> std::result::Result<tonic::Response<super::Episode>, tonic::Status>;
+        async fn list_episodes(
+            &self,
+            request: tonic::Request<super::ListEpisodesRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListEpisodesResponse>, tonic::Status>;
+        async fn next_up(
+            &self,
+            request: tonic::Request<super::NextUpRequest>,
+        ) -> std::result::Result<tonic::Response<super::NextUpResponse>, tonic::Status>;
+        async fn create_tag(
+            &self,
+            request: tonic::Request<super::CreateTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::Tag>, tonic::Status>;
+        async fn update_tag(
+            &self,
+            request: tonic::Request<super::UpdateTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::Tag>, tonic::Status>;
+        async fn delete_tag(
+            &self,
+            request: tonic::Request<super::DeleteTagRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeleteTagResponse>, tonic::Status>;
+        async fn list_tags(
+            &self,
+            request: tonic::Request<super::ListTagsRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListTagsResponse>, tonic::Status>;
+        async fn tag_affinity(
+            &self,
+            request: tonic::Request<super::TagAffinityRequest>,
+        ) -> std::result::Result<tonic::Response<super::TagAffinityResponse>, tonic::Status>;
+        async fn import_catalog(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::CatalogRecord>>,
+        ) -> std::result::Result<tonic::Response<super::ImportCatalogResponse>, tonic::Status>;
+        /// Server streaming response type for the ExportCatalog method.
+        type ExportCatalogStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::CatalogRecord, tonic::Status>,
+            > + std::marker::Send
+            + 'static;
+        /// all publishers, then all tags, then all contents, available or not
+        async fn export_catalog(
+            &self,
+            request: tonic::Request<super::ExportCatalogRequest>,
+        ) -> std::result::Result<tonic::Response<Self::ExportCatalogStream>, tonic::Status>;
+        async fn create_publisher(
+            &self,
+            request: tonic::Request<super::CreatePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status>;
+        async fn get_publisher(
+            &self,
+            request: tonic::Request<super::GetPublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status>;
+        async fn update_publisher(
+            &self,
+            request: tonic::Request<super::UpdatePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::Publisher>, tonic::Status>;
+        async fn delete_publisher(
+            &self,
+            request: tonic::Request<super::DeletePublisherRequest>,
+        ) -> std::result::Result<tonic::Response<super::DeletePublisherResponse>, tonic::Status>;
+        async fn list_publishers(
+            &self,
+            request: tonic::Request<super::ListPublishersRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListPublishersResponse>, tonic::Status>;
+        async fn follow_publisher(
+            &self,
+            request: tonic::Request<super::FollowRequest>,
+        ) -> std::result::Result<tonic::Response<super::FollowResponse>, tonic::Status>;
+        async fn unfollow_publisher(
+            &self,
+            request: tonic::Request<super::FollowRequest>,
+        ) -> std::result::Result<tonic::Response<super::FollowResponse>, tonic::Status>;
+        async fn list_followed(
+            &self,
+            request: tonic::Request<super::ListFollowedRequest>,
+        ) -> std::result::Result<tonic::Response<super::ListFollowedResponse>, tonic::Status>;
+        async fn new_from_followed(
+            &self,
+            request: tonic::Request<super::NewFromFollowedRequest>,
+        ) -> std::result::Result<tonic::Response<super::NewFromFollowedResponse>, tonic::Status>;
+        /// Server streaming response type for the WatchContent method.
+        type WatchContentStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::CatalogChange, tonic::Status>,
+            > + std::marker::Send
+            + 'static;
+        /// changes to contents and publishers, counters excluded
+        async fn watch_content(
+            &self,
+            request: tonic::Request<super::WatchContentRequest>,
+        ) -> std::result::Result<tonic::Response<Self::WatchContentStream>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct MetadataServer<T> {
+        inner: Arc<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    impl<T> MetadataServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for MetadataServer<T>
+    where
+        T: Metadata,
+        B: Body + std::marker::Send + 'static,
+        B::Error: Into<StdError> + std::marker::Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            match req.uri().path() {
+                "/crm_metadata.Metadata/Materialize" => {
+                    #[allow(non_camel_case_types)]
+                    struct MaterializeSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::StreamingService<super::MaterializeRequest> for MaterializeSvc<T> {
+                        type Response = super::MaterializeResponse;
+                        type ResponseStream = T::MaterializeStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::MaterializeRequest>>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::materialize(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = MaterializeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/CreateContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::CreateContentRequest> for CreateContentSvc<T> {
+                        type Response = super::Content;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::create_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/UpdateContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::UpdateContentRequest> for UpdateContentSvc<T> {
+                        type Response = super::Content;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::update_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/DeleteContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::DeleteContentRequest> for DeleteContentSvc<T> {
+                        type Response = super::DeleteContentResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::delete_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/PublishContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct PublishContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::PublishContentRequest>
+                        for PublishContentSvc<T>
+                    {
+                        type Response = super::Content;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PublishContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::publish_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PublishContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ArchiveContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct ArchiveContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ArchiveContentRequest>
+                        for ArchiveContentSvc<T>
+                    {
+                        type Response = super::Content;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ArchiveContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::archive_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ArchiveContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/GetContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::GetContentRequest> for GetContentSvc<T> {
+                        type Response = super::Content;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::get_content(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ListContents" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListContentsSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ListContentsRequest> for ListContentsSvc<T> {
+                        type Response = super::ListContentsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListContentsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::list_contents(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListContentsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/Recommend" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecommendSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::RecommendRequest> for RecommendSvc<T> {
+                        type Response = super::RecommendResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RecommendRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::recommend(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RecommendSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/RelatedContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct RelatedContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::RelatedContentRequest>
+                        for RelatedContentSvc<T>
+                    {
+                        type Response = super::RelatedContentResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::RelatedContentRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::related_content(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RelatedContentSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/RecordContentEvent" => {
+                    #[allow(non_camel_case_types)]
+                    struct RecordContentEventSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::ClientStreamingService<super::ContentEvent>
+                        for RecordContentEventSvc<T>
+                    {
+                        type Response = super::RecordContentEventResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::ContentEvent>>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::record_content_event(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RecordContentEventSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/CreateSeries" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateSeriesSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::CreateSeriesRequest> for CreateSeriesSvc<T> {
+                        type Response = super::Series;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateSeriesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::create_series(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateSeriesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/AddEpisode" => {
+                    #[allow(non_camel_case_types)]
+                    struct AddEpisodeSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::AddEpisodeRequest> for AddEpisodeSvc<T> {
+                        type Response = super::Episode;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AddEpisodeRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::add_episode(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AddEpisodeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ListEpisodes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListEpisodesSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ListEpisodesRequest> for ListEpisodesSvc<T> {
+                        type Response = super::ListEpisodesResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListEpisodesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::list_episodes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListEpisodesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/NextUp" => {
+                    #[allow(non_camel_case_types)]
+                    struct NextUpSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::NextUpRequest> for NextUpSvc<T> {
+                        type Response = super::NextUpResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::NextUpRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::next_up(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = NextUpSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/CreateTag" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreateTagSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::CreateTagRequest> for CreateTagSvc<T> {
+                        type Response = super::Tag;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreateTagRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::create_tag(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreateTagSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/UpdateTag" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateTagSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::UpdateTagRequest> for UpdateTagSvc<T> {
+                        type Response = super::Tag;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateTagRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::update_tag(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateTagSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/DeleteTag" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeleteTagSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::DeleteTagRequest> for DeleteTagSvc<T> {
+                        type Response = super::DeleteTagResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeleteTagRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::delete_tag(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeleteTagSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ListTags" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListTagsSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ListTagsRequest> for ListTagsSvc<T> {
+                        type Response = super::ListTagsResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListTagsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::list_tags(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListTagsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/TagAffinity" => {
+                    #[allow(non_camel_case_types)]
+                    struct TagAffinitySvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::TagAffinityRequest> for TagAffinitySvc<T> {
+                        type Response = super::TagAffinityResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TagAffinityRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut =
+                                async move { <T as Metadata>::tag_affinity(&inner, request).await };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TagAffinitySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ImportCatalog" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportCatalogSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::ClientStreamingService<super::CatalogRecord>
+                        for ImportCatalogSvc<T>
+                    {
+                        type Response = super::ImportCatalogResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::CatalogRecord>>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::import_catalog(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ImportCatalogSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.client_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ExportCatalog" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportCatalogSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata>
+                        tonic::server::ServerStreamingService<super::ExportCatalogRequest>
+                        for ExportCatalogSvc<T>
+                    {
+                        type Response = super::CatalogRecord;
+                        type ResponseStream = T::ExportCatalogStream;
+                        type Future =
+                            BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExportCatalogRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::export_catalog(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ExportCatalogSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/CreatePublisher" => {
+                    #[allow(non_camel_case_types)]
+                    struct CreatePublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::CreatePublisherRequest>
+                        for CreatePublisherSvc<T>
+                    {
+                        type Response = super::Publisher;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CreatePublisherRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::create_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CreatePublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/GetPublisher" => {
                     #[allow(non_camel_case_types)]
-                    struct MaterializeSvc<T: Metadata>(pub Arc<T>);
-                    impl<T: Metadata> tonic::server::StreamingService<super::MaterializeRequest> for MaterializeSvc<T> {
-                        type Response = super::Content;
-                        type ResponseStream = T::MaterializeStream;
+                    struct GetPublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::GetPublisherRequest> for GetPublisherSvc<T> {
+                        type Response = super::Publisher;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPublisherRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::get_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/UpdatePublisher" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdatePublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::UpdatePublisherRequest>
+                        for UpdatePublisherSvc<T>
+                    {
+                        type Response = super::Publisher;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdatePublisherRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::update_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdatePublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/DeletePublisher" => {
+                    #[allow(non_camel_case_types)]
+                    struct DeletePublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::DeletePublisherRequest>
+                        for DeletePublisherSvc<T>
+                    {
+                        type Response = super::DeletePublisherResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DeletePublisherRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::delete_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DeletePublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ListPublishers" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPublishersSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ListPublishersRequest>
+                        for ListPublishersSvc<T>
+                    {
+                        type Response = super::ListPublishersResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListPublishersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::list_publishers(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListPublishersSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/FollowPublisher" => {
+                    #[allow(non_camel_case_types)]
+                    struct FollowPublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::FollowRequest> for FollowPublisherSvc<T> {
+                        type Response = super::FollowResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FollowRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::follow_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FollowPublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/UnfollowPublisher" => {
+                    #[allow(non_camel_case_types)]
+                    struct UnfollowPublisherSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::FollowRequest> for UnfollowPublisherSvc<T> {
+                        type Response = super::FollowResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FollowRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::unfollow_publisher(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UnfollowPublisherSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/ListFollowed" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListFollowedSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::ListFollowedRequest> for ListFollowedSvc<T> {
+                        type Response = super::ListFollowedResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListFollowedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::list_followed(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListFollowedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/NewFromFollowed" => {
+                    #[allow(non_camel_case_types)]
+                    struct NewFromFollowedSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata> tonic::server::UnaryService<super::NewFromFollowedRequest>
+                        for NewFromFollowedSvc<T>
+                    {
+                        type Response = super::NewFromFollowedResponse;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::NewFromFollowedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Metadata>::new_from_followed(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = NewFromFollowedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/crm_metadata.Metadata/WatchContent" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchContentSvc<T: Metadata>(pub Arc<T>);
+                    impl<T: Metadata>
+                        tonic::server::ServerStreamingService<super::WatchContentRequest>
+                        for WatchContentSvc<T>
+                    {
+                        type Response = super::CatalogChange;
+                        type ResponseStream = T::WatchContentStream;
                         type Future =
                             BoxFuture<tonic::Response<Self::ResponseStream>, tonic::Status>;
                         fn call(
                             &mut self,
-                            request: tonic::Request<tonic::Streaming<super::MaterializeRequest>>,
+                            request: tonic::Request<super::WatchContentRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
-                            let fut =
-                                async move { <T as Metadata>::materialize(&inner, request).await };
+                            let fut = async move {
+                                <T as Metadata>::watch_content(&inner, request).await
+                            };
                             Box::pin(fut)
                         }
                     }
@@ -311,7 +3004,7 @@ pub mod metadata_server {
                     let max_encoding_message_size = self.max_encoding_message_size;
                     let inner = self.inner.clone();
                     let fut = async move {
-                        let method = MaterializeSvc(inner);
+                        let method = WatchContentSvc(inner);
                         let codec = tonic::codec::ProstCodec::default();
                         let mut grpc = tonic::server::Grpc::new(codec)
                             .apply_compression_config(
@@ -322,7 +3015,7 @@ pub mod metadata_server {
                                 max_decoding_message_size,
                                 max_encoding_message_size,
                             );
-                        let res = grpc.streaming(method, req).await;
+                        let res = grpc.server_streaming(method, req).await;
                         Ok(res)
                     };
                     Box::pin(fut)
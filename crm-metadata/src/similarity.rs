@@ -0,0 +1,159 @@
+use std::{collections::HashSet, time::Duration};
+
+use anyhow::Result;
+use duckdb::{params, Connection};
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::config::SimilarityConfig;
+
+/// jaccard similarity over the users that watched each content
+const SIMILARITY_SQL: &str = r#"with counts as (
+  select content_id, count(*) as n from watches group by content_id
+), pairs as (
+  select a.content_id as id, b.content_id as related_id, count(*) as both_count
+  from watches a join watches b on a.user_id = b.user_id and a.content_id <> b.content_id
+  group by all having count(*) >= ?
+)
+select p.id, p.related_id, p.both_count / (ca.n + cb.n - p.both_count) as score
+from pairs p
+  join counts ca on ca.content_id = p.id
+  join counts cb on cb.content_id = p.related_id
+qualify row_number() over (partition by p.id order by score desc, p.related_id) <= ?"#;
+
+// recent_watched and finished of one user
+type History = (Option<Vec<i32>>, Option<Vec<i32>>);
+
+pub struct SimilarityJob {
+    pool: PgPool,
+    config: SimilarityConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct SimilarityReport {
+    pub users: u64,
+    pub contents: u64,
+    pub neighbors: u64,
+}
+
+impl SimilarityJob {
+    pub async fn try_new(db_url: &str, config: SimilarityConfig) -> Result<Self> {
+        let pool = PgPool::connect(db_url).await?;
+        Ok(Self { pool, config })
+    }
+
+    /// run the job every `interval` seconds in the background
+    pub fn schedule(self) {
+        let interval = Duration::from_secs(self.config.interval);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.run().await {
+                    Ok(report) => info!("similarity run finished: {:?}", report),
+                    Err(e) => warn!("similarity run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// recompute the neighbors from user_stats and replace related_contents with them
+    pub async fn run(&self) -> Result<SimilarityReport> {
+        let histories: Vec<History> = sqlx::query_as(
+            "select recent_watched, finished from user_stats where anonymized_at is null",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        let users = histories.len() as u64;
+        let config = self.config.clone();
+        // duckdb is blocking, keep it off the runtime threads
+        let neighbors =
+            tokio::task::spawn_blocking(move || compute_neighbors(histories, &config)).await??;
+
+        let mut ids = Vec::with_capacity(neighbors.len());
+        let mut related_ids = Vec::with_capacity(neighbors.len());
+        let mut scores = Vec::with_capacity(neighbors.len());
+        for (id, related_id, score) in neighbors {
+            ids.push(id);
+            related_ids.push(related_id);
+            scores.push(score);
+        }
+        let contents = ids.iter().collect::<HashSet<_>>().len() as u64;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("delete from related_contents")
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "insert into related_contents(content_id, related_id, score) select * from unnest($1::int[], $2::int[], $3::float8[])",
+        )
+        .bind(&ids)
+        .bind(&related_ids)
+        .bind(&scores)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(SimilarityReport {
+            users,
+            contents,
+            neighbors: ids.len() as u64,
+        })
+    }
+}
+
+fn compute_neighbors(
+    histories: Vec<History>,
+    config: &SimilarityConfig,
+) -> Result<Vec<(i32, i32, f64)>> {
+    let conn = Connection::open_in_memory()?;
+    conn.execute_batch("create table watches(user_id integer, content_id integer)")?;
+    {
+        let mut appender = conn.appender("watches")?;
+        for (user_id, (recent_watched, finished)) in histories.into_iter().enumerate() {
+            // a content counts once per user no matter which arrays it is in
+            let watched = recent_watched
+                .into_iter()
+                .chain(finished)
+                .flatten()
+                .collect::<HashSet<_>>();
+            for content_id in watched {
+                appender.append_row(params![user_id as i32, content_id])?;
+            }
+        }
+    }
+    let mut stmt = conn.prepare(SIMILARITY_SQL)?;
+    let rows = stmt
+        .query_map(params![config.min_users, config.top_k], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_neighbors_should_work() -> Result<()> {
+        let config = SimilarityConfig {
+            top_k: 1,
+            min_users: 1,
+            interval: 0,
+        };
+        let histories = vec![
+            (Some(vec![1, 2]), None),
+            (Some(vec![1]), Some(vec![2])),
+            (None, Some(vec![1, 3, 3])),
+        ];
+        let mut neighbors = compute_neighbors(histories, &config)?;
+        neighbors.sort_by_key(|(id, _, _)| *id);
+        // 1 and 2 share 2 of 3 users, 1 and 3 only 1 of 3
+        assert_eq!(
+            neighbors,
+            vec![(1, 2, 2.0 / 3.0), (2, 1, 2.0 / 3.0), (3, 1, 1.0 / 3.0)]
+        );
+        Ok(())
+    }
+}
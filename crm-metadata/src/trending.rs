@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::config::TrendingConfig;
+
+/// rows a concurrent refresh holds are left to it
+const REFRESH_STALE_SQL: &str = r#"update contents set trending = content_trending(id), trending_day = (now() at time zone 'utc')::date
+where id in (select id from contents where trending > 0 and trending_day < (now() at time zone 'utc')::date
+  order by id for update skip locked)"#;
+
+/// decays trending of contents that got no events since an earlier day
+pub struct TrendingJob {
+    pool: PgPool,
+    config: TrendingConfig,
+}
+
+impl TrendingJob {
+    pub async fn try_new(db_url: &str, config: TrendingConfig) -> Result<Self> {
+        let pool = PgPool::connect(db_url).await?;
+        Ok(Self { pool, config })
+    }
+
+    /// run the job every `interval` seconds in the background
+    pub fn schedule(self) {
+        let interval = Duration::from_secs(self.config.interval);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.run().await {
+                    Ok(n) => info!("trending refreshed for {} contents", n),
+                    Err(e) => warn!("trending refresh failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// recompute the trending values computed before today, returns the contents refreshed
+    pub async fn run(&self) -> Result<u64> {
+        Ok(refresh_stale(&self.pool).await?)
+    }
+}
+
+pub(crate) async fn refresh_stale(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let ret = sqlx::query(REFRESH_STALE_SQL).execute(pool).await?;
+    Ok(ret.rows_affected())
+}
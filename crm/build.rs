@@ -4,6 +4,7 @@ use proto_builder_trait::tonic::BuilderAttributes;
 use std::fs;
 
 fn main() -> Result<()> {
+    println!("cargo:rerun-if-changed=migrations");
     fs::create_dir_all("src/pb")?;
     let builder = tonic_build::configure();
     // builder.out_dir("src/pb").compile_protos(
@@ -16,7 +17,9 @@ fn main() -> Result<()> {
 
     builder
         .out_dir("src/pb")
+        .extern_path(".crm_metadata", "::crm_metadata::pb")
         .with_derive_builder(&["WelcomeRequest", "RecallRequest", "RemindRequest"], None)
+        .with_serde(&["LocaleVariant"], true, true, None)
         .with_sqlx_from_row(&["User"], None)
         .with_field_attributes(
             &["User.name", "User.email", "RawQueryRequest.query"],
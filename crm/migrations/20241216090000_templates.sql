@@ -0,0 +1,16 @@
+-- email templates referenced by campaigns
+create table if NOT EXISTS templates(
+  id serial NOT NULL PRIMARY KEY,
+  name varchar(64) NOT NULL UNIQUE,
+  description text NOT NULL DEFAULT '',
+  created_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+create table if NOT EXISTS template_versions(
+  template_id int NOT NULL REFERENCES templates(id) ON DELETE CASCADE,
+  version int NOT NULL,
+  subject varchar(256) NOT NULL,
+  html text NOT NULL,
+  text text NOT NULL,
+  published_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY(template_id, version)
+);
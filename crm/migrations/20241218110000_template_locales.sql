@@ -0,0 +1,2 @@
+-- per-locale variants of a template version, [{locale, subject, html, text}]
+alter table template_versions add column if NOT EXISTS variants jsonb NOT NULL DEFAULT '[]';
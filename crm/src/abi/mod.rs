@@ -1,7 +1,8 @@
 mod auth;
+mod template;
 use crate::{
     pb::{
-        RecallRequest, RecallResponse, RemindRequest, RemindResponse, WelcomeRequest,
+        RecallRequest, RecallResponse, RemindRequest, RemindResponse, TopContents, WelcomeRequest,
         WelcomeResponse,
     },
     CrmService,
@@ -10,10 +11,13 @@ use anyhow::Result;
 pub use auth::DecodingKey;
 use chrono::{Duration, Utc};
 use crm_metadata::{
-    pb::{Content, MaterializeRequest},
+    pb::{
+        materialize_response::Result as MaterializeResult, Content, ContentType,
+        ListContentsRequest, MaterializeRequest, NextUpRequest,
+    },
     Tpl,
 };
-use futures::{StreamExt, TryStreamExt};
+use futures::{future, StreamExt, TryStreamExt};
 use notification::{EmailMessage, Msg, SendRequest};
 use prost_types::Timestamp;
 use std::collections::HashSet;
@@ -25,6 +29,10 @@ use tracing::warn;
 use user_state::pb::{QueryRequestBuilder, TimeQuery, User};
 use uuid::Uuid;
 
+const DEFAULT_TOP_CONTENTS: u32 = 3;
+/// next up lookups in flight while reminding
+const NEXT_UP_CONCURRENCY: usize = 16;
+
 impl CrmService {
     pub async fn welcome(
         &self,
@@ -52,22 +60,10 @@ impl CrmService {
             self.user_state.clone().query(query).await?;
         let mut user_stream = user_res.into_inner();
 
-        //materialize request
-        let content_ids = request.content_ids.clone();
-        let metarequest: HashSet<_> = content_ids
-            .iter()
-            .map(|x| MaterializeRequest { id: *x })
-            .collect();
-        let request_stream = tokio_stream::iter(metarequest.into_iter());
-        let request = Request::new(request_stream);
-
-        let meta_res = self.metadata.clone().materialize(request).await?;
-        let meta_stream = meta_res.into_inner();
-        //stream to vec
-        let contents = meta_stream
-            .try_collect::<Vec<_>>()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let contents = match request.top {
+            Some(top) => self.top_contents(top).await?,
+            None => self.materialize_contents(&request.content_ids).await?,
+        };
         // let contents = Arc::new(contents);
         let mut notification = self.notification.clone();
 
@@ -82,12 +78,18 @@ impl CrmService {
         //     notification.send(req).await?;
         // }
 
+        let (tpl, template) = self
+            .resolve_template(request.template.as_ref(), "welcome")
+            .await?;
         let (tx, rx) = mpsc::channel(1024);
         let sender_email = self.config.server.sender_email.clone();
         tokio::spawn(async move {
             while let Some(Ok(user)) = user_stream.next().await {
-                let req =
-                    gen_send_request("Welcome".to_string(), sender_email.clone(), user, &contents);
+                let Some(req) =
+                    gen_send_request(&tpl, &template, sender_email.clone(), user, &contents)
+                else {
+                    continue;
+                };
                 if let Err(e) = tx.send(req).await {
                     warn!("Failed to send message: {:?}", e);
                 };
@@ -123,27 +125,20 @@ impl CrmService {
             .map_err(|e| Status::internal(e.to_string()))?;
         let user_res = self.user_state.clone().query(query).await?;
         let mut user_stream = user_res.into_inner();
-        let content_ids = request.content_ids.clone();
-        let metarequest: HashSet<_> = content_ids
-            .iter()
-            .map(|x| MaterializeRequest { id: *x })
-            .collect();
-        let request_stream = tokio_stream::iter(metarequest.into_iter());
-
-        let request = Request::new(request_stream);
-        let meta_res = self.metadata.clone().materialize(request).await?;
-        let meta_stream = meta_res.into_inner();
-        let contents = meta_stream
-            .try_collect::<Vec<_>>()
-            .await
-            .map_err(|e| Status::internal(e.to_string()))?;
+        let contents = self.materialize_contents(&request.content_ids).await?;
         let mut notification = self.notification.clone();
+        let (tpl, template) = self
+            .resolve_template(request.template.as_ref(), "recall")
+            .await?;
         let (tx, rx) = mpsc::channel(1024);
         let sender_email = self.config.server.sender_email.clone();
         tokio::spawn(async move {
             while let Some(Ok(user)) = user_stream.next().await {
-                let req =
-                    gen_send_request("Recall".to_string(), sender_email.clone(), user, &contents);
+                let Some(req) =
+                    gen_send_request(&tpl, &template, sender_email.clone(), user, &contents)
+                else {
+                    continue;
+                };
                 if let Err(e) = tx.send(req).await {
                     warn!("Failed to send message: {:?}", e);
                 };
@@ -176,20 +171,46 @@ impl CrmService {
             .build()
             .map_err(|e| Status::internal(e.to_string()))?;
         let user_res = self.user_state.clone().query(query).await?;
-        let mut user_stream = user_res.into_inner();
+        let user_stream = user_res.into_inner();
+        let (tpl, template) = self
+            .resolve_template(request.template.as_ref(), "remind")
+            .await?;
         let (tx, rx) = mpsc::channel(1024);
         let sender_email = self.config.server.sender_email.clone();
         let mut notification = self.notification.clone();
+        let metadata = self.metadata.clone();
         tokio::spawn(async move {
-            while let Some(Ok(user)) = user_stream.next().await {
-                let req =SendRequest {
-                    msg: Some(Msg::Email(EmailMessage {
-                        message_id: Uuid::new_v4().to_string(),
-                        sender:sender_email.clone(),
-                        recipients: user.email,
-                        subject:"Remind!!!".to_string(),
-                        body: "Hope you could be well! There were more contents start but not finished. Wecome Back to us".to_string(),
-                    })),
+            let mut next_ups = user_stream
+                .take_while(|user| future::ready(user.is_ok()))
+                .filter_map(|user| future::ready(user.ok()))
+                .map(|user| {
+                    let mut metadata = metadata.clone();
+                    async move {
+                        // nudge towards the next episode of what the user is watching
+                        let req = NextUpRequest {
+                            email: user.email.clone(),
+                        };
+                        let contents = match metadata.next_up(req).await {
+                            Ok(ret) => ret
+                                .into_inner()
+                                .next_up
+                                .into_iter()
+                                .filter_map(|n| n.episode?.content)
+                                .collect(),
+                            Err(e) => {
+                                warn!("Failed to get next up for {}: {}", user.email, e);
+                                vec![]
+                            }
+                        };
+                        (user, contents)
+                    }
+                })
+                .buffer_unordered(NEXT_UP_CONCURRENCY);
+            while let Some((user, contents)) = next_ups.next().await {
+                let Some(req) =
+                    gen_send_request(&tpl, &template, sender_email.clone(), user, &contents)
+                else {
+                    continue;
                 };
 
                 if let Err(e) = tx.send(req).await {
@@ -202,22 +223,76 @@ impl CrmService {
         let ret = RemindResponse { id: request_id };
         Ok(Response::new(ret))
     }
+
+    /// the best contents of a type by the given sort, e.g. the top trending shorts
+    async fn top_contents(&self, top: TopContents) -> Result<Vec<Content>, Status> {
+        let n = match top.n {
+            0 => DEFAULT_TOP_CONTENTS,
+            n => n,
+        };
+        let req = ListContentsRequest {
+            // no type picks from the whole catalog
+            content_type: (top.content_type() != ContentType::Unspecified)
+                .then_some(top.content_type),
+            sort: top.sort,
+            page_size: n,
+            ..Default::default()
+        };
+        let ret = self.metadata.clone().list_contents(req).await?;
+        Ok(ret.into_inner().contents)
+    }
+
+    /// materialize the given content ids, unknown or unavailable ones are skipped with a warning
+    async fn materialize_contents(&self, content_ids: &[u32]) -> Result<Vec<Content>, Status> {
+        let metarequest: HashSet<_> = content_ids
+            .iter()
+            .map(|x| MaterializeRequest {
+                id: *x,
+                ..Default::default()
+            })
+            .collect();
+        let request_stream = tokio_stream::iter(metarequest);
+        let request = Request::new(request_stream);
+
+        let meta_res = self.metadata.clone().materialize(request).await?;
+        let mut meta_stream = meta_res.into_inner();
+        let mut contents = vec![];
+        while let Some(res) = meta_stream.try_next().await? {
+            match res.result {
+                Some(MaterializeResult::Content(content)) => contents.push(content),
+                Some(MaterializeResult::Error(e)) => {
+                    warn!("Skip content {}: {}", e.id, e.reason)
+                }
+                None => {}
+            }
+        }
+        Ok(contents)
+    }
 }
 
+/// render the email for one user, a user the template fails for is skipped with a warning
 fn gen_send_request(
-    subject: String,
+    tpl: &Tpl,
+    template: &str,
     sender: String,
     user: User,
     content: &[Content],
-) -> SendRequest {
-    let tpl = Tpl(content);
-    SendRequest {
+) -> Option<SendRequest> {
+    let rendered = match tpl.render(template, &user, content) {
+        Ok(rendered) => rendered,
+        Err(e) => {
+            warn!("Failed to render {} for {}: {}", template, user.email, e);
+            return None;
+        }
+    };
+    Some(SendRequest {
         msg: Some(Msg::Email(EmailMessage {
             message_id: Uuid::new_v4().to_string(),
             sender,
             recipients: user.email,
-            subject,
-            body: tpl.to_body(),
+            subject: rendered.subject,
+            body: rendered.text,
+            html: rendered.html,
         })),
-    }
+    })
 }
@@ -0,0 +1,513 @@
+use std::{collections::HashSet, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use crm_metadata::Tpl;
+use futures::TryStreamExt;
+use prost_types::Timestamp;
+use sqlx::types::Json;
+use tonic::{Response, Status};
+use user_state::{normalize_locale, pb::RawQueryRequest};
+
+use crate::{
+    pb::{
+        CreateTemplateRequest, ListTemplatesRequest, ListTemplatesResponse, LocaleVariant, Preview,
+        PreviewTemplateRequest, PreviewTemplateResponse, PublishVersionRequest, Template,
+        TemplateRef, TemplateVersion,
+    },
+    CrmService, ServiceResult,
+};
+
+const DEFAULT_SAMPLE_SIZE: u32 = 3;
+const MAX_SAMPLE_SIZE: u32 = 20;
+
+#[derive(Debug, sqlx::FromRow)]
+struct TemplateRow {
+    id: i32,
+    name: String,
+    description: String,
+    latest_version: Option<i32>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct VersionRow {
+    name: String,
+    subject: String,
+    html: String,
+    text: String,
+    variants: Json<Vec<LocaleVariant>>,
+}
+
+impl CrmService {
+    pub async fn create_template(&self, mut req: CreateTemplateRequest) -> ServiceResult<Template> {
+        if req.name.is_empty() {
+            return Err(Status::invalid_argument("name is required"));
+        }
+        // locale variants are registered as `name.locale`
+        if req.name.contains('.') {
+            return Err(Status::invalid_argument(format!(
+                "Invalid template name: {:?}",
+                req.name
+            )));
+        }
+        validate(&req.subject, &req.html, &req.text, &mut req.variants)
+            .map_err(Status::invalid_argument)?;
+        let mut tx = self.pool.begin().await.map_err(internal)?;
+        let (id, created_at): (i32, DateTime<Utc>) = sqlx::query_as(
+            "insert into templates(name, description) values($1, $2) returning id, created_at",
+        )
+        .bind(&req.name)
+        .bind(&req.description)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_unique_violation() => {
+                Status::already_exists(format!("Template {} already exists", req.name))
+            }
+            e => internal(e),
+        })?;
+        sqlx::query(
+            "insert into template_versions(template_id, version, subject, html, text, variants) values($1, 1, $2, $3, $4, $5)",
+        )
+        .bind(id)
+        .bind(&req.subject)
+        .bind(&req.html)
+        .bind(&req.text)
+        .bind(Json(&req.variants))
+        .execute(&mut *tx)
+        .await
+        .map_err(internal)?;
+        tx.commit().await.map_err(internal)?;
+        Ok(Response::new(Template {
+            id: id as _,
+            name: req.name,
+            description: req.description,
+            latest_version: 1,
+            created_at: Some(utc_to_ts(created_at)),
+        }))
+    }
+
+    pub async fn publish_version(
+        &self,
+        mut req: PublishVersionRequest,
+    ) -> ServiceResult<TemplateVersion> {
+        validate(&req.subject, &req.html, &req.text, &mut req.variants)
+            .map_err(Status::invalid_argument)?;
+        let (version, published_at): (i32, DateTime<Utc>) = sqlx::query_as(
+            r#"insert into template_versions(template_id, version, subject, html, text, variants)
+            select $1, coalesce(max(version), 0) + 1, $2, $3, $4, $5 from template_versions where template_id = $1
+            returning version, published_at"#,
+        )
+        .bind(req.template_id as i32)
+        .bind(&req.subject)
+        .bind(&req.html)
+        .bind(&req.text)
+        .bind(Json(&req.variants))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                Status::not_found(format!("Template {} not found", req.template_id))
+            }
+            // someone else published the same version number first
+            sqlx::Error::Database(e) if e.is_unique_violation() => {
+                Status::aborted(format!("Concurrent publish on template {}", req.template_id))
+            }
+            e => internal(e),
+        })?;
+        Ok(Response::new(TemplateVersion {
+            template_id: req.template_id,
+            version: version as _,
+            subject: req.subject,
+            html: req.html,
+            text: req.text,
+            published_at: Some(utc_to_ts(published_at)),
+            variants: req.variants,
+        }))
+    }
+
+    pub async fn list_templates(
+        &self,
+        _req: ListTemplatesRequest,
+    ) -> ServiceResult<ListTemplatesResponse> {
+        let rows = sqlx::query_as::<_, TemplateRow>(
+            r#"select t.id, t.name, t.description, t.created_at, max(v.version) as latest_version
+            from templates t left join template_versions v on v.template_id = t.id
+            group by t.id order by t.id"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal)?;
+        Ok(Response::new(ListTemplatesResponse {
+            templates: rows.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    /// render the template for a few random users, a render error is returned as is
+    pub async fn preview_template(
+        &self,
+        req: PreviewTemplateRequest,
+    ) -> ServiceResult<PreviewTemplateResponse> {
+        let template = req
+            .template
+            .as_ref()
+            .ok_or_else(|| Status::invalid_argument("template is required"))?;
+        let (tpl, name) = self.resolve_template(Some(template), "").await?;
+        let sample_size = match req.sample_size {
+            0 => DEFAULT_SAMPLE_SIZE,
+            n => n.min(MAX_SAMPLE_SIZE),
+        };
+        let query = RawQueryRequest {
+            query: format!(
+                "select email, name, phone, locale from user_stats where anonymized_at is null order by random() limit {}",
+                sample_size
+            ),
+        };
+        let users = self
+            .user_state
+            .clone()
+            .raw_query(query)
+            .await?
+            .into_inner()
+            .try_collect::<Vec<_>>()
+            .await?;
+        let contents = self.materialize_contents(&req.content_ids).await?;
+
+        let mut previews = Vec::with_capacity(users.len());
+        for user in users {
+            let rendered = tpl
+                .render(&name, &user, &contents)
+                .map_err(|e| Status::invalid_argument(format!("Failed to render: {}", e)))?;
+            previews.push(Preview {
+                email: user.email,
+                subject: rendered.subject,
+                html: rendered.html,
+                text: rendered.text,
+            });
+        }
+        Ok(Response::new(PreviewTemplateResponse { previews }))
+    }
+
+    /// the stored template version to render, or the built-in one when none is referenced
+    pub(super) async fn resolve_template(
+        &self,
+        template: Option<&TemplateRef>,
+        builtin: &str,
+    ) -> Result<(Arc<Tpl>, String), Status> {
+        let Some(template) = template else {
+            return Ok((self.tpl.clone(), builtin.to_string()));
+        };
+        let row = sqlx::query_as::<_, VersionRow>(
+            r#"select t.name, v.subject, v.html, v.text, v.variants
+            from template_versions v join templates t on t.id = v.template_id
+            where v.template_id = $1 and ($2 = 0 or v.version = $2)
+            order by v.version desc limit 1"#,
+        )
+        .bind(template.template_id as i32)
+        .bind(template.version as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(internal)?
+        .ok_or_else(|| {
+            Status::not_found(format!(
+                "Template {} version {} not found",
+                template.template_id, template.version
+            ))
+        })?;
+        let mut tpl = Tpl::empty();
+        let broken = |e| Status::internal(format!("Stored template is broken: {}", e));
+        tpl.add(&row.name, &row.subject, &row.html, &row.text)
+            .map_err(broken)?;
+        for v in &row.variants.0 {
+            tpl.add_locale(&row.name, &v.locale, &v.subject, &v.html, &v.text)
+                .map_err(broken)?;
+        }
+        Ok((Arc::new(tpl), row.name))
+    }
+}
+
+/// only syntax can be checked here, undefined variables show up in PreviewTemplate.
+/// locales are normalized like user-state stores them, so `zh_cn` reaches `zh-CN` users
+fn validate(
+    subject: &str,
+    html: &str,
+    text: &str,
+    variants: &mut [LocaleVariant],
+) -> Result<(), String> {
+    if subject.is_empty() {
+        return Err("subject is required".to_string());
+    }
+    let mut tpl = Tpl::empty();
+    tpl.add("validate", subject, html, text)
+        .map_err(|e| format!("Invalid template: {}", e))?;
+    let mut locales = HashSet::new();
+    for v in variants {
+        v.locale =
+            normalize_locale(&v.locale).ok_or_else(|| format!("Invalid locale: {:?}", v.locale))?;
+        if !locales.insert(v.locale.clone()) {
+            return Err(format!("Duplicate locale: {}", v.locale));
+        }
+        if v.subject.is_empty() {
+            return Err(format!("subject is required for {}", v.locale));
+        }
+        tpl.add_locale("validate", &v.locale, &v.subject, &v.html, &v.text)
+            .map_err(|e| format!("Invalid template for {}: {}", v.locale, e))?;
+    }
+    Ok(())
+}
+
+impl From<TemplateRow> for Template {
+    fn from(row: TemplateRow) -> Self {
+        Template {
+            id: row.id as _,
+            name: row.name,
+            description: row.description,
+            latest_version: row.latest_version.unwrap_or_default() as _,
+            created_at: Some(utc_to_ts(row.created_at)),
+        }
+    }
+}
+
+fn utc_to_ts(t: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: t.timestamp(),
+        nanos: t.timestamp_subsec_nanos() as _,
+    }
+}
+
+fn internal(e: sqlx::Error) -> Status {
+    Status::internal(format!("Failed to access templates: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use crm_metadata::pb::metadata_client::MetadataClient;
+    use notification::pb::notification_client::NotificationClient;
+    use sqlx::PgPool;
+    use tonic::transport::Endpoint;
+    use user_state::pb::{user_stats_client::UserStatsClient, User};
+
+    use super::*;
+    use crate::AppConfig;
+
+    /// the registry only needs the database, the other services are connected on first use
+    async fn service() -> Result<CrmService> {
+        let config = AppConfig::try_load()?;
+        let pool = PgPool::connect(&config.server.db_url).await?;
+        sqlx::migrate!().set_ignore_missing(true).run(&pool).await?;
+        let lazy = |url: &str| Endpoint::from_shared(url.to_string()).map(|e| e.connect_lazy());
+        Ok(CrmService {
+            user_state: UserStatsClient::new(lazy(&config.server.user_stats)?),
+            notification: NotificationClient::new(lazy(&config.server.notification)?),
+            metadata: MetadataClient::new(lazy(&config.server.metadata)?),
+            tpl: Arc::new(Tpl::new()),
+            pool,
+            config,
+        })
+    }
+
+    fn variant(locale: &str, subject: &str) -> LocaleVariant {
+        LocaleVariant {
+            locale: locale.to_string(),
+            subject: subject.to_string(),
+            html: "<p>{{ user.name }}</p>".to_string(),
+            text: "{{ user.name }}".to_string(),
+        }
+    }
+
+    fn user(locale: Option<&str>) -> User {
+        User {
+            email: "tyr@acme.org".to_string(),
+            name: "Tyr".to_string(),
+            locale: locale.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    async fn subject(
+        service: &CrmService,
+        template_id: u32,
+        version: u32,
+        locale: Option<&str>,
+    ) -> Result<String, Status> {
+        let template = TemplateRef {
+            template_id,
+            version,
+        };
+        let (tpl, name) = service.resolve_template(Some(&template), "").await?;
+        let rendered = tpl
+            .render(&name, &user(locale), &[])
+            .map_err(|e| Status::internal(e.to_string()))?;
+        Ok(rendered.subject)
+    }
+
+    #[test]
+    fn validate_should_work() {
+        assert!(validate("Hi {{ user.name }}", "<p>Hi</p>", "Hi", &mut []).is_ok());
+        assert_eq!(
+            validate("", "", "", &mut []).unwrap_err(),
+            "subject is required"
+        );
+        assert!(validate("Hi", "{{ user.name", "", &mut [])
+            .unwrap_err()
+            .starts_with("Invalid template:"));
+        assert_eq!(
+            validate("Hi", "", "", &mut [variant("de", "")]).unwrap_err(),
+            "subject is required for de"
+        );
+        assert!(validate("Hi", "", "", &mut [variant("de", "{% if %}")])
+            .unwrap_err()
+            .starts_with("Invalid template for de:"));
+    }
+
+    #[test]
+    fn validate_should_normalize_locales() {
+        let mut variants = vec![variant("zh_cn", "Hi"), variant("EN", "Hi")];
+        assert!(validate("Hi", "<p>{{ user.name }}</p>", "", &mut variants).is_ok());
+        let locales = variants
+            .iter()
+            .map(|v| v.locale.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(locales, ["zh-CN", "en"]);
+
+        assert_eq!(
+            validate("Hi", "", "", &mut [variant("english", "Hi")]).unwrap_err(),
+            "Invalid locale: \"english\""
+        );
+        assert_eq!(
+            validate("Hi", "", "", &mut [variant("zh.CN", "Hi")]).unwrap_err(),
+            "Invalid locale: \"zh.CN\""
+        );
+        assert_eq!(
+            validate(
+                "Hi",
+                "",
+                "",
+                &mut [variant("zh_cn", "Hi"), variant("zh-CN", "Hi")]
+            )
+            .unwrap_err(),
+            "Duplicate locale: zh-CN"
+        );
+    }
+
+    #[tokio::test]
+    async fn template_registry_should_work() -> Result<()> {
+        let service = service().await?;
+        let name = format!("registry-{}", nanoid::nanoid!(8));
+        let template = service
+            .create_template(CreateTemplateRequest {
+                name: name.clone(),
+                subject: "v1".to_string(),
+                html: "<p>v1</p>".to_string(),
+                text: "v1".to_string(),
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        assert_eq!(template.latest_version, 1);
+
+        let version = service
+            .publish_version(PublishVersionRequest {
+                template_id: template.id,
+                subject: "v2".to_string(),
+                html: "<p>v2</p>".to_string(),
+                text: "v2".to_string(),
+                variants: vec![variant("zh_cn", "v2 zh")],
+            })
+            .await?
+            .into_inner();
+        assert_eq!(version.version, 2);
+        assert_eq!(version.variants[0].locale, "zh-CN");
+
+        // version 0 resolves the latest, the stored locale serves users of that locale
+        assert_eq!(subject(&service, template.id, 0, None).await?, "v2");
+        assert_eq!(
+            subject(&service, template.id, 0, Some("zh-CN")).await?,
+            "v2 zh"
+        );
+        assert_eq!(
+            subject(&service, template.id, 1, Some("zh-CN")).await?,
+            "v1"
+        );
+
+        let ret = service
+            .create_template(CreateTemplateRequest {
+                name,
+                subject: "dup".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(ret.unwrap_err().code(), tonic::Code::AlreadyExists);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn stored_template_should_not_use_builtin_variants() -> Result<()> {
+        let service = service().await?;
+        // names are unique, reuse the template left by an earlier run
+        let ret = service
+            .create_template(CreateTemplateRequest {
+                name: "welcome".to_string(),
+                subject: "stored".to_string(),
+                ..Default::default()
+            })
+            .await;
+        let template_id = match ret {
+            Ok(template) => template.into_inner().id,
+            Err(e) if e.code() == tonic::Code::AlreadyExists => {
+                sqlx::query_scalar::<_, i32>("select id from templates where name = 'welcome'")
+                    .fetch_one(&service.pool)
+                    .await? as u32
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let version = service
+            .publish_version(PublishVersionRequest {
+                template_id,
+                subject: "stored".to_string(),
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        let zh = subject(&service, template_id, version.version, Some("zh-CN")).await?;
+        assert_eq!(zh, "stored");
+
+        let ret = service
+            .create_template(CreateTemplateRequest {
+                name: "welcome.zh".to_string(),
+                subject: "dotted".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(ret.unwrap_err().code(), tonic::Code::InvalidArgument);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn missing_template_should_not_be_found() -> Result<()> {
+        let service = service().await?;
+        let template = service
+            .create_template(CreateTemplateRequest {
+                name: format!("missing-{}", nanoid::nanoid!(8)),
+                subject: "v1".to_string(),
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+
+        let ret = subject(&service, i32::MAX as u32, 0, None).await;
+        assert_eq!(ret.unwrap_err().code(), tonic::Code::NotFound);
+        let ret = subject(&service, template.id, 2, None).await;
+        assert_eq!(ret.unwrap_err().code(), tonic::Code::NotFound);
+        let ret = service
+            .publish_version(PublishVersionRequest {
+                template_id: i32::MAX as u32,
+                subject: "v1".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert_eq!(ret.unwrap_err().code(), tonic::Code::NotFound);
+        Ok(())
+    }
+}
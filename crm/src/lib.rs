@@ -1,13 +1,17 @@
 use abi::DecodingKey;
 use anyhow::{Ok, Result};
 
-use crm_metadata::pb::metadata_client::MetadataClient;
+use crm_metadata::{pb::metadata_client::MetadataClient, Tpl};
 
 use notification::pb::notification_client::NotificationClient;
 use pb::{
     crm_server::{Crm, CrmServer},
-    RecallRequest, RecallResponse, RemindRequest, RemindResponse, WelcomeRequest, WelcomeResponse,
+    CreateTemplateRequest, ListTemplatesRequest, ListTemplatesResponse, PreviewTemplateRequest,
+    PreviewTemplateResponse, PublishVersionRequest, RecallRequest, RecallResponse, RemindRequest,
+    RemindResponse, Template, TemplateVersion, WelcomeRequest, WelcomeResponse,
 };
+use sqlx::PgPool;
+use std::sync::Arc;
 use tonic::{
     async_trait, service::interceptor::InterceptedService, transport::Channel, Request, Response,
     Status,
@@ -22,9 +26,11 @@ pub use config::AppConfig;
 
 pub struct CrmService {
     config: AppConfig,
+    pool: PgPool,
     user_state: UserStatsClient<Channel>,
     notification: NotificationClient<Channel>,
     metadata: MetadataClient<Channel>,
+    tpl: Arc<Tpl>,
 }
 
 impl CrmService {
@@ -32,11 +38,15 @@ impl CrmService {
         let user_state = UserStatsClient::connect(config.server.user_stats.clone()).await?;
         let notification = NotificationClient::connect(config.server.notification.clone()).await?;
         let metadata = MetadataClient::connect(config.server.metadata.clone()).await?;
+        let pool = PgPool::connect(&config.server.db_url).await?;
+        sqlx::migrate!().set_ignore_missing(true).run(&pool).await?;
         Ok(Self {
             config,
+            pool,
             user_state,
             notification,
             metadata,
+            tpl: Arc::new(Tpl::new()),
         })
     }
     pub fn into_server(self) -> Result<InterceptedService<CrmServer<CrmService>, DecodingKey>> {
@@ -62,4 +72,28 @@ impl Crm for CrmService {
     async fn remind(&self, request: Request<RemindRequest>) -> ServiceResult<RemindResponse> {
         self.remind(request.into_inner()).await
     }
+    async fn create_template(
+        &self,
+        request: Request<CreateTemplateRequest>,
+    ) -> ServiceResult<Template> {
+        self.create_template(request.into_inner()).await
+    }
+    async fn publish_version(
+        &self,
+        request: Request<PublishVersionRequest>,
+    ) -> ServiceResult<TemplateVersion> {
+        self.publish_version(request.into_inner()).await
+    }
+    async fn list_templates(
+        &self,
+        request: Request<ListTemplatesRequest>,
+    ) -> ServiceResult<ListTemplatesResponse> {
+        self.list_templates(request.into_inner()).await
+    }
+    async fn preview_template(
+        &self,
+        request: Request<PreviewTemplateRequest>,
+    ) -> ServiceResult<PreviewTemplateResponse> {
+        self.preview_template(request.into_inner()).await
+    }
 }
@@ -146,6 +146,7 @@ mod tests {
                 recipients: SafeEmail().fake(),
                 subject: "Hello".to_string(),
                 body: "Hello".to_string(),
+                html: "<p>Hello</p>".to_string(),
             }
         }
     }
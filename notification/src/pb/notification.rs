@@ -14,10 +14,14 @@ pub struct EmailMessage {
     pub sender: ::prost::alloc::string::String,
     #[prost(string, tag = "3")]
     pub recipients: ::prost::alloc::string::String,
+    /// plain text part
     #[prost(string, tag = "4")]
     pub body: ::prost::alloc::string::String,
     #[prost(string, tag = "5")]
     pub message_id: ::prost::alloc::string::String,
+    /// html part, empty for plain text only emails
+    #[prost(string, tag = "6")]
+    pub html: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SmsMessage {
@@ -21,6 +21,7 @@ fn email_fake() -> EmailMessage {
         recipients: SafeEmail().fake(),
         subject: "Hello".to_string(),
         body: "Hello".to_string(),
+        html: "<p>Hello</p>".to_string(),
     }
 }
 
@@ -4,6 +4,7 @@ use proto_builder_trait::tonic::BuilderAttributes;
 use std::fs;
 
 fn main() -> Result<()> {
+    println!("cargo:rerun-if-changed=migrations");
     fs::create_dir_all("src/pb")?;
     let builder = tonic_build::configure();
     // builder.out_dir("src/pb").compile_protos(
@@ -29,10 +30,16 @@ fn main() -> Result<()> {
                 "RawQueryRequest",
                 "TimeQuery",
                 "IdQuery",
+                "EngagementQuery",
+                "SetAttributesRequest",
             ],
             None,
         )
         .with_sqlx_from_row(&["User"], None)
+        .with_field_attributes(
+            &["User.device_ids", "User.phone", "User.locale"],
+            &[r#"#[sqlx(default)]"#],
+        )
         .with_field_attributes(
             &["User.name", "User.email", "RawQueryRequest.query"],
             &[r#"#[builder(setter(into))]"#],
@@ -49,6 +56,18 @@ fn main() -> Result<()> {
             &["QueryRequest.ids"],
             &[r#"#[builder(setter(each(name="id", into)))]"#],
         )
+        .with_field_attributes(
+            &["QueryRequest.attributes"],
+            &[r#"#[builder(setter(each(name="attribute", into)))]"#],
+        )
+        .with_field_attributes(
+            &["SetAttributesRequest.attributes"],
+            &[r#"#[builder(setter(each(name="attribute", into)))]"#],
+        )
+        .with_field_attributes(
+            &["SetAttributesRequest.email"],
+            &[r#"#[builder(setter(into))]"#],
+        )
         .compile_protos(
             &[
                 "../protos/user_stats/messages.proto",
@@ -0,0 +1,8 @@
+-- engagement score and churn-risk bucket, refreshed by the RefreshEngagement rpc
+create type churn_risk as enum('low', 'medium', 'high');
+alter table user_stats
+  add column if NOT EXISTS engagement_score real,
+  add column if NOT EXISTS churn_risk churn_risk,
+  add column if NOT EXISTS scored_at timestamptz;
+CREATE index if NOT EXISTS user_stats_engagement_score_idx ON user_stats(engagement_score);
+CREATE index if NOT EXISTS user_stats_churn_risk_idx ON user_stats(churn_risk);
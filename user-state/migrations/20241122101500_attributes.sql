@@ -0,0 +1,4 @@
+-- free-form profile attributes, segmentable through typed attribute filters
+alter table user_stats
+  add column if NOT EXISTS attributes jsonb NOT NULL DEFAULT '{}';
+CREATE index if NOT EXISTS user_stats_attributes_idx ON user_stats using GIN(attributes);
@@ -0,0 +1,10 @@
+-- devices registered for in-app delivery
+create type platform as enum('ios', 'android', 'web');
+create table if NOT EXISTS devices(
+  id varchar(128) NOT NULL PRIMARY KEY,
+  email varchar(128) NOT NULL REFERENCES user_stats(email) ON DELETE CASCADE ON UPDATE CASCADE,
+  platform platform NOT NULL,
+  created_at timestamptz DEFAULT CURRENT_TIMESTAMP,
+  last_seen_at timestamptz DEFAULT CURRENT_TIMESTAMP
+);
+CREATE index if NOT EXISTS devices_email_idx ON devices(email);
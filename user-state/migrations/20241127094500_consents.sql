@@ -0,0 +1,13 @@
+-- phone numbers in E.164 and per-channel consent
+create type channel as enum('email', 'sms', 'in_app');
+alter table user_stats
+  add column if NOT EXISTS phone varchar(16) CHECK (phone ~ '^\+[1-9][0-9]{1,14}$');
+create table if NOT EXISTS channel_consents(
+  email varchar(128) NOT NULL REFERENCES user_stats(email) ON DELETE CASCADE ON UPDATE CASCADE,
+  channel channel NOT NULL,
+  opted_in boolean NOT NULL,
+  consented_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  source varchar(64) NOT NULL,
+  PRIMARY KEY(email, channel)
+);
+CREATE index if NOT EXISTS channel_consents_channel_idx ON channel_consents(channel, opted_in);
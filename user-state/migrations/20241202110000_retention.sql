@@ -0,0 +1,15 @@
+-- retention policy runs, one row per run including dry runs
+create type retention_action as enum('delete', 'anonymize');
+alter table user_stats
+  add column if NOT EXISTS anonymized_at timestamptz;
+create table if NOT EXISTS retention_runs(
+  id bigserial NOT NULL PRIMARY KEY,
+  action retention_action NOT NULL,
+  inactive_days int NOT NULL,
+  dry_run boolean NOT NULL,
+  matched bigint NOT NULL DEFAULT 0,
+  affected bigint NOT NULL DEFAULT 0,
+  started_at timestamptz NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  finished_at timestamptz,
+  error text
+);
@@ -0,0 +1,2 @@
+-- preferred language of the user, a BCP 47 tag like en or zh-CN
+alter table user_stats add column if NOT EXISTS locale varchar(16);
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+
+use serde_json::{json, Map, Value};
+use tonic::{Response, Status};
+
+use super::range_query;
+
+use crate::{
+    pb::{
+        attribute_query::Predicate, attribute_value, AttributeQuery, AttributeValue,
+        SetAttributesRequest, SetAttributesResponse,
+    },
+    ServiceResult, UserStatsService,
+};
+
+impl UserStatsService {
+    pub async fn set_attributes(
+        &self,
+        req: SetAttributesRequest,
+    ) -> ServiceResult<SetAttributesResponse> {
+        let attributes = to_json(req.attributes).map_err(Status::invalid_argument)?;
+        let ret = sqlx::query(
+            "update user_stats set attributes = (attributes || $2) - $3::text[] where email = $1",
+        )
+        .bind(&req.email)
+        .bind(attributes)
+        .bind(&req.removed)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to set attributes: {}", e)))?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("User {} not found", req.email)));
+        }
+        Ok(Response::new(SetAttributesResponse { email: req.email }))
+    }
+}
+
+fn to_json(attributes: HashMap<String, AttributeValue>) -> Result<Value, String> {
+    let mut map = Map::new();
+    for (k, v) in attributes {
+        let v = match v.value {
+            Some(attribute_value::Value::StringValue(s)) => Value::String(s),
+            Some(attribute_value::Value::NumberValue(n)) => json!(n),
+            Some(attribute_value::Value::BoolValue(b)) => Value::Bool(b),
+            None => return Err(format!("attribute {} has no value", k)),
+        };
+        map.insert(k, v);
+    }
+    Ok(Value::Object(map))
+}
+
+pub(crate) fn attributes_query(name: &str, query: AttributeQuery) -> Result<String, String> {
+    let key = quote(name);
+    let sql = match query.predicate {
+        Some(Predicate::Equals(s)) => {
+            format!(
+                " and attributes @> {}",
+                quote(&json!({ name: s }).to_string())
+            )
+        }
+        Some(Predicate::Is(b)) => {
+            format!(
+                " and attributes @> {}",
+                quote(&json!({ name: b }).to_string())
+            )
+        }
+        Some(Predicate::Exists(true)) => format!(" and attributes ? {}", key),
+        Some(Predicate::Exists(false)) => format!(" and not attributes ? {}", key),
+        Some(Predicate::Range(range)) => {
+            // cast only numeric values so that a string stored under the same key does not fail the query
+            let value = format!(
+                "(case when jsonb_typeof(attributes->{key}) = 'number' then (attributes->>{key})::numeric end)"
+            );
+            let bounds = range_query(name, &value, range.min, range.max)?;
+            format!(" and {} is not null{}", value, bounds)
+        }
+        None => "".to_string(),
+    };
+    Ok(sql)
+}
+
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
@@ -0,0 +1,157 @@
+use chrono::{DateTime, Utc};
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{
+        Channel, Consent, GetConsentsRequest, GetConsentsResponse, SetPhoneRequest,
+        SetPhoneResponse, UpdateConsentRequest,
+    },
+    ServiceResult, UserStatsService,
+};
+
+use super::utc_to_ts;
+
+#[derive(Debug, sqlx::FromRow)]
+struct ConsentRow {
+    channel: String,
+    opted_in: bool,
+    consented_at: DateTime<Utc>,
+    source: String,
+}
+
+impl UserStatsService {
+    pub async fn set_phone(&self, req: SetPhoneRequest) -> ServiceResult<SetPhoneResponse> {
+        // an empty phone clears the number
+        let phone = if req.phone.is_empty() {
+            None
+        } else if is_e164(&req.phone) {
+            Some(req.phone.as_str())
+        } else {
+            return Err(Status::invalid_argument(format!(
+                "{} is not an E.164 phone number",
+                req.phone
+            )));
+        };
+        let ret = sqlx::query("update user_stats set phone = $2 where email = $1")
+            .bind(&req.email)
+            .bind(phone)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to set phone: {}", e)))?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("User {} not found", req.email)));
+        }
+        Ok(Response::new(SetPhoneResponse {
+            email: req.email,
+            phone: req.phone,
+        }))
+    }
+
+    pub async fn update_consent(&self, req: UpdateConsentRequest) -> ServiceResult<Consent> {
+        let channel = channel_name(req.channel())
+            .ok_or_else(|| Status::invalid_argument("channel is required"))?;
+        if req.source.is_empty() {
+            return Err(Status::invalid_argument("source is required"));
+        }
+        let row = sqlx::query_as::<_, ConsentRow>(
+            r#"insert into channel_consents(email, channel, opted_in, source) values($1, $2::channel, $3, $4)
+            on conflict(email, channel) do update set opted_in = excluded.opted_in, source = excluded.source, consented_at = now()
+            returning channel::text, opted_in, consented_at, source"#,
+        )
+        .bind(&req.email)
+        .bind(channel)
+        .bind(req.opted_in)
+        .bind(&req.source)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                Status::not_found(format!("User {} not found", req.email))
+            }
+            e => Status::internal(format!("Failed to update consent: {}", e)),
+        })?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn get_consents(
+        &self,
+        req: GetConsentsRequest,
+    ) -> ServiceResult<GetConsentsResponse> {
+        let rows = sqlx::query_as::<_, ConsentRow>(
+            "select channel::text, opted_in, consented_at, source from channel_consents where email = $1 order by channel",
+        )
+        .bind(&req.email)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to get consents: {}", e)))?;
+        Ok(Response::new(GetConsentsResponse {
+            email: req.email,
+            consents: rows.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+/// only users that opted in for the channel are kept, sms additionally needs a phone number
+pub(crate) fn consent_query(channel: Channel) -> String {
+    let Some(name) = channel_name(channel) else {
+        return "".to_string();
+    };
+    let mut sql = format!(
+        " and exists (select 1 from channel_consents c where c.email = user_stats.email and c.channel = '{}' and c.opted_in)",
+        name
+    );
+    if channel == Channel::Sms {
+        sql.push_str(" and phone is not null");
+    }
+    sql
+}
+
+impl From<ConsentRow> for Consent {
+    fn from(row: ConsentRow) -> Self {
+        let channel = match row.channel.as_str() {
+            "email" => Channel::Email,
+            "sms" => Channel::Sms,
+            "in_app" => Channel::InApp,
+            _ => Channel::Unspecified,
+        };
+        Consent {
+            channel: channel as i32,
+            opted_in: row.opted_in,
+            consented_at: Some(utc_to_ts(row.consented_at)),
+            source: row.source,
+        }
+    }
+}
+
+fn channel_name(channel: Channel) -> Option<&'static str> {
+    match channel {
+        Channel::Email => Some("email"),
+        Channel::Sms => Some("sms"),
+        Channel::InApp => Some("in_app"),
+        Channel::Unspecified => None,
+    }
+}
+
+fn is_e164(phone: &str) -> bool {
+    let Some(digits) = phone.strip_prefix('+') else {
+        return false;
+    };
+    (2..=15).contains(&digits.len())
+        && digits.chars().all(|c| c.is_ascii_digit())
+        && !digits.starts_with('0')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_e164_should_work() {
+        assert!(is_e164("+8613800138000"));
+        assert!(is_e164("+14155552671"));
+        assert!(!is_e164("13800138000"));
+        assert!(!is_e164("+0123456"));
+        assert!(!is_e164("+1 415 555 2671"));
+        assert!(!is_e164("+1234567890123456"));
+    }
+}
@@ -0,0 +1,107 @@
+use chrono::{DateTime, Utc};
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{
+        Device, ListDevicesRequest, ListDevicesResponse, Platform, RegisterDeviceRequest,
+        UnregisterDeviceRequest, UnregisterDeviceResponse,
+    },
+    ServiceResult, UserStatsService,
+};
+
+use super::utc_to_ts;
+
+pub(crate) const DEVICE_IDS_COLUMN: &str =
+    "array(select d.id::text from devices d where d.email = user_stats.email) as device_ids";
+
+#[derive(Debug, sqlx::FromRow)]
+struct DeviceRow {
+    id: String,
+    email: String,
+    platform: String,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+}
+
+impl UserStatsService {
+    pub async fn register_device(&self, req: RegisterDeviceRequest) -> ServiceResult<Device> {
+        let platform = platform_name(req.platform())
+            .ok_or_else(|| Status::invalid_argument("platform is required"))?;
+        // registering a known device again moves it to the given user and refreshes last_seen_at
+        let row = sqlx::query_as::<_, DeviceRow>(
+            r#"insert into devices(id, email, platform) values($1, $2, $3::platform)
+            on conflict(id) do update set email = excluded.email, platform = excluded.platform, last_seen_at = now()
+            returning id, email, platform::text, created_at, last_seen_at"#,
+        )
+        .bind(&req.id)
+        .bind(&req.email)
+        .bind(platform)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.is_foreign_key_violation() => {
+                Status::not_found(format!("User {} not found", req.email))
+            }
+            e => Status::internal(format!("Failed to register device: {}", e)),
+        })?;
+        Ok(Response::new(row.into()))
+    }
+
+    pub async fn unregister_device(
+        &self,
+        req: UnregisterDeviceRequest,
+    ) -> ServiceResult<UnregisterDeviceResponse> {
+        let ret = sqlx::query("delete from devices where id = $1")
+            .bind(&req.id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to unregister device: {}", e)))?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("Device {} not found", req.id)));
+        }
+        Ok(Response::new(UnregisterDeviceResponse { id: req.id }))
+    }
+
+    pub async fn list_devices(
+        &self,
+        req: ListDevicesRequest,
+    ) -> ServiceResult<ListDevicesResponse> {
+        let rows = sqlx::query_as::<_, DeviceRow>(
+            "select id, email, platform::text, created_at, last_seen_at from devices where email = $1 order by last_seen_at desc",
+        )
+        .bind(&req.email)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to list devices: {}", e)))?;
+        Ok(Response::new(ListDevicesResponse {
+            devices: rows.into_iter().map(Into::into).collect(),
+        }))
+    }
+}
+
+impl From<DeviceRow> for Device {
+    fn from(row: DeviceRow) -> Self {
+        let platform = match row.platform.as_str() {
+            "ios" => Platform::Ios,
+            "android" => Platform::Android,
+            "web" => Platform::Web,
+            _ => Platform::Unspecified,
+        };
+        Device {
+            id: row.id,
+            email: row.email,
+            platform: platform as i32,
+            created_at: Some(utc_to_ts(row.created_at)),
+            last_seen_at: Some(utc_to_ts(row.last_seen_at)),
+        }
+    }
+}
+
+fn platform_name(platform: Platform) -> Option<&'static str> {
+    match platform {
+        Platform::Ios => Some("ios"),
+        Platform::Android => Some("android"),
+        Platform::Web => Some("web"),
+        Platform::Unspecified => None,
+    }
+}
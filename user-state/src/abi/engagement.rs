@@ -0,0 +1,73 @@
+use tonic::{Response, Status};
+
+use super::range_query;
+
+use crate::{
+    pb::{ChurnRisk, EngagementQuery, RefreshEngagementResponse},
+    ServiceResult, UserStatsService,
+};
+
+// recency decays with a 30 days time constant, frequency and depth saturate at 50 items.
+// churn risk only looks at how long ago the user was last seen, so that a high score
+// together with a high risk selects valuable users that are slipping away.
+const REFRESH_SQL: &str = r#"
+with signals as (
+  select email,
+    extract(epoch from now() - greatest(last_visited_at, last_watched_at)) / 86400 as recency,
+    least(coalesce(cardinality(recent_watched), 0), 50) / 50.0 as frequency,
+    least(coalesce(cardinality(finished), 0) + 0.5 * coalesce(cardinality(started_but_not_finished), 0), 50) / 50.0 as depth
+  from user_stats
+)
+update user_stats u set
+  engagement_score = round((100 * (0.4 * coalesce(exp(-s.recency / 30), 0) + 0.3 * s.frequency + 0.3 * s.depth))::numeric, 2),
+  churn_risk = case
+    when s.recency is null or s.recency > 30 then 'high'::churn_risk
+    when s.recency > 7 then 'medium'::churn_risk
+    else 'low'::churn_risk
+  end,
+  scored_at = now()
+from signals s
+where u.email = s.email
+"#;
+
+impl UserStatsService {
+    pub async fn refresh_engagement(&self) -> ServiceResult<RefreshEngagementResponse> {
+        let ret = sqlx::query(REFRESH_SQL)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to refresh engagement: {}", e)))?;
+        Ok(Response::new(RefreshEngagementResponse {
+            updated: ret.rows_affected(),
+        }))
+    }
+}
+
+pub(crate) fn engagement_query(query: Option<EngagementQuery>) -> Result<String, String> {
+    let Some(query) = query else {
+        return Ok("".to_string());
+    };
+    let mut sql = range_query(
+        "engagement_score",
+        "engagement_score",
+        query.min_score,
+        query.max_score,
+    )?;
+    let risks = query
+        .churn_risks()
+        .filter_map(churn_risk_name)
+        .map(|name| format!("'{}'", name))
+        .collect::<Vec<_>>();
+    if !risks.is_empty() {
+        sql.push_str(&format!(" and churn_risk in ({})", risks.join(",")));
+    }
+    Ok(sql)
+}
+
+fn churn_risk_name(risk: ChurnRisk) -> Option<&'static str> {
+    match risk {
+        ChurnRisk::Low => Some("low"),
+        ChurnRisk::Medium => Some("medium"),
+        ChurnRisk::High => Some("high"),
+        ChurnRisk::Unspecified => None,
+    }
+}
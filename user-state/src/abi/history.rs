@@ -0,0 +1,48 @@
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{GetHistoryRequest, History},
+    ServiceResult, UserStatsService,
+};
+
+#[derive(Debug, sqlx::FromRow)]
+struct HistoryRow {
+    email: String,
+    recent_watched: Option<Vec<i32>>,
+    viewed_but_not_started: Option<Vec<i32>>,
+    started_but_not_finished: Option<Vec<i32>>,
+    finished: Option<Vec<i32>>,
+}
+
+impl UserStatsService {
+    pub async fn get_history(&self, req: GetHistoryRequest) -> ServiceResult<History> {
+        let row = sqlx::query_as::<_, HistoryRow>(
+            r#"select email, recent_watched, viewed_but_not_started, started_but_not_finished, finished
+            from user_stats where email = $1 and anonymized_at is null"#,
+        )
+        .bind(&req.email)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Status::internal(format!("Failed to get history: {}", e)))?
+        .ok_or_else(|| Status::not_found(format!("User {} not found", req.email)))?;
+        Ok(Response::new(row.into()))
+    }
+}
+
+impl From<HistoryRow> for History {
+    fn from(row: HistoryRow) -> Self {
+        let ids = |ids: Option<Vec<i32>>| {
+            ids.unwrap_or_default()
+                .into_iter()
+                .map(|id| id as u32)
+                .collect()
+        };
+        History {
+            email: row.email,
+            recent_watched: ids(row.recent_watched),
+            viewed_but_not_started: ids(row.viewed_but_not_started),
+            started_but_not_finished: ids(row.started_but_not_finished),
+            finished: ids(row.finished),
+        }
+    }
+}
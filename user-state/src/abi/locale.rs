@@ -0,0 +1,68 @@
+use tonic::{Response, Status};
+
+use crate::{
+    pb::{SetLocaleRequest, SetLocaleResponse},
+    ServiceResult, UserStatsService,
+};
+
+impl UserStatsService {
+    pub async fn set_locale(&self, req: SetLocaleRequest) -> ServiceResult<SetLocaleResponse> {
+        // an empty locale clears it
+        let locale = if req.locale.is_empty() {
+            None
+        } else {
+            Some(normalize_locale(&req.locale).ok_or_else(|| {
+                Status::invalid_argument(format!("{} is not a valid locale", req.locale))
+            })?)
+        };
+        let ret = sqlx::query("update user_stats set locale = $2 where email = $1")
+            .bind(&req.email)
+            .bind(&locale)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Status::internal(format!("Failed to set locale: {}", e)))?;
+        if ret.rows_affected() == 0 {
+            return Err(Status::not_found(format!("User {} not found", req.email)));
+        }
+        Ok(Response::new(SetLocaleResponse {
+            email: req.email,
+            locale: locale.unwrap_or_default(),
+        }))
+    }
+}
+
+/// accept `language[-_]REGION` in any case, return it as `language-REGION`
+pub fn normalize_locale(locale: &str) -> Option<String> {
+    let mut parts = locale.split(['-', '_']);
+    let language = parts.next()?;
+    if !(2..=3).contains(&language.len()) || !language.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let mut ret = language.to_ascii_lowercase();
+    if let Some(region) = parts.next() {
+        if region.len() != 2 || !region.chars().all(|c| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        ret.push('-');
+        ret.push_str(&region.to_ascii_uppercase());
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_locale_should_work() {
+        assert_eq!(normalize_locale("zh_cn"), Some("zh-CN".to_string()));
+        assert_eq!(normalize_locale("EN"), Some("en".to_string()));
+        assert_eq!(normalize_locale("pt-BR"), Some("pt-BR".to_string()));
+        assert_eq!(normalize_locale("english"), None);
+        assert_eq!(normalize_locale("zh-Hans-CN"), None);
+        assert_eq!(normalize_locale("z1"), None);
+    }
+}
@@ -1,5 +1,17 @@
+mod attributes;
+mod consents;
+mod devices;
+mod engagement;
+mod history;
+mod locale;
+
+use attributes::attributes_query;
 use chrono::{DateTime, TimeZone, Utc};
+use consents::consent_query;
+use devices::DEVICE_IDS_COLUMN;
+use engagement::engagement_query;
 use futures::stream;
+pub use locale::normalize_locale;
 use prost_types::Timestamp;
 use tonic::{Response, Status};
 
@@ -10,7 +22,16 @@ use crate::{
 
 impl UserStatsService {
     pub async fn query(&self, query: QueryRequest) -> ServiceResult<ResponseStream> {
-        let mut sql = "select email,name from user_stats where 1=1 ".to_string();
+        let consented_channel = query.consented_channel();
+        let mut sql = if query.with_devices {
+            format!(
+                "select email,name,phone,locale,{} from user_stats where anonymized_at is null ",
+                DEVICE_IDS_COLUMN
+            )
+        } else {
+            "select email,name,phone,locale from user_stats where anonymized_at is null "
+                .to_string()
+        };
         let time_conditions = query
             .timestamps
             .into_iter()
@@ -25,6 +46,16 @@ impl UserStatsService {
             .collect::<Vec<_>>()
             .join(" ");
         sql.push_str(&id_conditions);
+        sql.push_str(&engagement_query(query.engagement).map_err(Status::invalid_argument)?);
+        let attribute_conditions = query
+            .attributes
+            .into_iter()
+            .map(|(k, v)| attributes_query(&k, v))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?
+            .join(" ");
+        sql.push_str(&attribute_conditions);
+        sql.push_str(&consent_query(consented_channel));
 
         // Implement your logic here
         self.raw_query(RawQueryRequest { query: sql }).await
@@ -54,6 +85,22 @@ fn ids_query(name: &str, ids: Vec<u32>) -> String {
     }
     format!(" and array{:?} <@ {}", ids, name)
 }
+/// `min <= expr <= max`, the bounds are formatted into sql so NaN or inf are rejected
+fn range_query<T>(name: &str, expr: &str, min: Option<T>, max: Option<T>) -> Result<String, String>
+where
+    T: Into<f64> + std::fmt::Display + Copy,
+{
+    let mut sql = String::new();
+    for (bound, op, label) in [(min, ">=", "min"), (max, "<=", "max")] {
+        let Some(bound) = bound else { continue };
+        if !bound.into().is_finite() {
+            return Err(format!("Invalid {} for {}: {}", label, name, bound));
+        }
+        sql.push_str(&format!(" and {} {} {}", expr, op, bound));
+    }
+    Ok(sql)
+}
+
 fn timestamp_query(name: &str, before: Option<Timestamp>, after: Option<Timestamp>) -> String {
     if before.is_none() && after.is_none() {
         return "".to_string();
@@ -75,6 +122,13 @@ fn ts_to_utc(ts: Timestamp) -> DateTime<Utc> {
     Utc.timestamp_opt(ts.seconds, ts.nanos as _).unwrap()
 }
 
+fn utc_to_ts(dt: DateTime<Utc>) -> Timestamp {
+    Timestamp {
+        seconds: dt.timestamp(),
+        nanos: dt.timestamp_subsec_nanos() as _,
+    }
+}
+
 // #[cfg(test)]
 // #[allow(unused)]
 // mod tests {
@@ -0,0 +1,25 @@
+use anyhow::{Context, Result};
+use tracing::{info, level_filters::LevelFilter};
+use tracing_subscriber::{
+    fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt as _, Layer as _,
+};
+use user_state::{AppConfig, RetentionJob};
+
+// usage: retention [--dry-run]
+#[tokio::main]
+async fn main() -> Result<()> {
+    let layer = Layer::new().with_filter(LevelFilter::INFO);
+    tracing_subscriber::registry().with(layer).init();
+    let dry_run = std::env::args().any(|arg| arg == "--dry-run");
+    let config = AppConfig::try_load().context("load config failed")?;
+    let retention = config
+        .retention
+        .context("retention is not configured in user_stat.yaml")?;
+    let job = RetentionJob::try_new(&config.server.db_url, retention).await?;
+    let report = job.run(dry_run).await?;
+    info!(
+        "retention run {} ({:?}, dry run: {}): {} users matched, {} affected",
+        report.run_id, report.action, report.dry_run, report.matched, report.affected
+    );
+    Ok(())
+}
@@ -6,6 +6,7 @@ use serde::{Deserialize, Serialize};
 pub struct AppConfig {
     pub server: ServerConfig,
     pub auth: AuthConfig,
+    pub retention: Option<RetentionConfig>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -20,6 +21,25 @@ pub struct ServerConfig {
     pub duck_db: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct RetentionConfig {
+    /// users not visited for this many days are affected
+    pub inactive_days: u32,
+    pub action: RetentionAction,
+    pub batch_size: u32,
+    /// seconds between scheduled runs, 0 disables the background task
+    #[serde(default)]
+    pub interval: u64,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "retention_action", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RetentionAction {
+    Delete,
+    Anonymize,
+}
+
 impl AppConfig {
     pub fn try_load() -> Result<Self> {
         // read from ./app.yml, or /etc/config/app.yml, or from env CHAT_CONFIG
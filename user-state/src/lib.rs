@@ -1,14 +1,22 @@
 use std::{ops::Deref, pin::Pin, sync::Arc};
 mod abi;
 mod config;
+mod retention;
 use anyhow::Result;
 use pb::User;
 pub mod pb;
-pub use config::AppConfig;
+pub use abi::normalize_locale;
+pub use config::{AppConfig, RetentionAction, RetentionConfig};
 use pb::{
     user_stats_server::{UserStats, UserStatsServer},
-    QueryRequest, RawQueryRequest,
+    Consent, Device, GetConsentsRequest, GetConsentsResponse, GetHistoryRequest, History,
+    ListDevicesRequest, ListDevicesResponse, QueryRequest, RawQueryRequest,
+    RefreshEngagementRequest, RefreshEngagementResponse, RegisterDeviceRequest,
+    SetAttributesRequest, SetAttributesResponse, SetLocaleRequest, SetLocaleResponse,
+    SetPhoneRequest, SetPhoneResponse, UnregisterDeviceRequest, UnregisterDeviceResponse,
+    UpdateConsentRequest,
 };
+pub use retention::{RetentionJob, RetentionReport};
 use sqlx::PgPool;
 use tonic::{Request, Response, Status};
 
@@ -24,6 +32,7 @@ pub struct UserStateServiceInner {
 impl UserStatsService {
     pub async fn new(config: AppConfig) -> Result<Self> {
         let pool = PgPool::connect(&config.server.db_url).await?;
+        sqlx::migrate!().set_ignore_missing(true).run(&pool).await?;
         // let duck_config = Config::default().access_mode(AccessMode::ReadWrite)?;
         // let duck = Connection::open_with_flags(&config.server.duck_db, duck_config)?;
 
@@ -71,4 +80,71 @@ impl UserStats for UserStatsService {
         // Implement your logic here
         self.raw_query(request.into_inner()).await
     }
+
+    async fn set_attributes(
+        &self,
+        request: Request<SetAttributesRequest>,
+    ) -> ServiceResult<SetAttributesResponse> {
+        self.set_attributes(request.into_inner()).await
+    }
+
+    async fn refresh_engagement(
+        &self,
+        _request: Request<RefreshEngagementRequest>,
+    ) -> ServiceResult<RefreshEngagementResponse> {
+        self.refresh_engagement().await
+    }
+
+    async fn register_device(
+        &self,
+        request: Request<RegisterDeviceRequest>,
+    ) -> ServiceResult<Device> {
+        self.register_device(request.into_inner()).await
+    }
+
+    async fn unregister_device(
+        &self,
+        request: Request<UnregisterDeviceRequest>,
+    ) -> ServiceResult<UnregisterDeviceResponse> {
+        self.unregister_device(request.into_inner()).await
+    }
+
+    async fn list_devices(
+        &self,
+        request: Request<ListDevicesRequest>,
+    ) -> ServiceResult<ListDevicesResponse> {
+        self.list_devices(request.into_inner()).await
+    }
+
+    async fn set_phone(
+        &self,
+        request: Request<SetPhoneRequest>,
+    ) -> ServiceResult<SetPhoneResponse> {
+        self.set_phone(request.into_inner()).await
+    }
+
+    async fn update_consent(
+        &self,
+        request: Request<UpdateConsentRequest>,
+    ) -> ServiceResult<Consent> {
+        self.update_consent(request.into_inner()).await
+    }
+
+    async fn get_consents(
+        &self,
+        request: Request<GetConsentsRequest>,
+    ) -> ServiceResult<GetConsentsResponse> {
+        self.get_consents(request.into_inner()).await
+    }
+
+    async fn get_history(&self, request: Request<GetHistoryRequest>) -> ServiceResult<History> {
+        self.get_history(request.into_inner()).await
+    }
+
+    async fn set_locale(
+        &self,
+        request: Request<SetLocaleRequest>,
+    ) -> ServiceResult<SetLocaleResponse> {
+        self.set_locale(request.into_inner()).await
+    }
 }
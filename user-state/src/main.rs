@@ -6,7 +6,7 @@ use tracing::{info, level_filters::LevelFilter};
 use tracing_subscriber::{
     fmt::Layer, layer::SubscriberExt, util::SubscriberInitExt as _, Layer as _,
 };
-use user_state::{AppConfig, UserStatsService};
+use user_state::{AppConfig, RetentionJob, UserStatsService};
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -14,7 +14,14 @@ async fn main() -> Result<()> {
     tracing_subscriber::registry().with(layer).init();
     let config = AppConfig::try_load().context("load config failed")?;
     let addr: std::net::SocketAddr = format!("[::1]:{}", &config.server.port).parse().unwrap();
+    let retention = config.retention.clone().filter(|r| r.interval > 0);
+    let db_url = config.server.db_url.clone();
+    // the job expects the tables the service migrates on start
     let service = UserStatsService::new(config).await?.into_server();
+    if let Some(retention) = retention {
+        info!("Retention job scheduled every {}s", retention.interval);
+        RetentionJob::try_new(&db_url, retention).await?.schedule();
+    }
 
     info!("UserStatsService listening on {}", addr);
     Server::builder().add_service(service).serve(addr).await?;
@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::config::{RetentionAction, RetentionConfig};
+
+const INACTIVE: &str = "coalesce(last_visited_at, created_at) < now() - make_interval(days => $1) and anonymized_at is null";
+
+pub struct RetentionJob {
+    pool: PgPool,
+    config: RetentionConfig,
+}
+
+#[derive(Debug, Clone)]
+pub struct RetentionReport {
+    pub run_id: i64,
+    pub action: RetentionAction,
+    pub dry_run: bool,
+    pub matched: u64,
+    pub affected: u64,
+}
+
+impl RetentionJob {
+    pub async fn try_new(db_url: &str, config: RetentionConfig) -> Result<Self> {
+        let pool = PgPool::connect(db_url).await?;
+        Ok(Self { pool, config })
+    }
+
+    /// run the job every `interval` seconds in the background
+    pub fn schedule(self) {
+        let interval = Duration::from_secs(self.config.interval);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match self.run(false).await {
+                    Ok(report) => info!("retention run finished: {:?}", report),
+                    Err(e) => warn!("retention run failed: {}", e),
+                }
+            }
+        });
+    }
+
+    /// every run is recorded in retention_runs, a dry run only counts the matched users
+    pub async fn run(&self, dry_run: bool) -> Result<RetentionReport> {
+        let run_id: i64 = sqlx::query_scalar(
+            "insert into retention_runs(action, inactive_days, dry_run) values($1, $2, $3) returning id",
+        )
+        .bind(self.config.action)
+        .bind(self.config.inactive_days as i32)
+        .bind(dry_run)
+        .fetch_one(&self.pool)
+        .await?;
+
+        let ret = self.apply(dry_run).await;
+        let (matched, affected, error) = match &ret {
+            Ok((matched, affected)) => (*matched, *affected, None),
+            Err(e) => (0, 0, Some(e.to_string())),
+        };
+        sqlx::query(
+            "update retention_runs set matched = $2, affected = $3, error = $4, finished_at = now() where id = $1",
+        )
+        .bind(run_id)
+        .bind(matched as i64)
+        .bind(affected as i64)
+        .bind(error)
+        .execute(&self.pool)
+        .await?;
+        ret?;
+
+        Ok(RetentionReport {
+            run_id,
+            action: self.config.action,
+            dry_run,
+            matched,
+            affected,
+        })
+    }
+
+    async fn apply(&self, dry_run: bool) -> Result<(u64, u64)> {
+        let matched: i64 = sqlx::query_scalar(&format!(
+            "select count(*) from user_stats where {}",
+            INACTIVE
+        ))
+        .bind(self.config.inactive_days as i32)
+        .fetch_one(&self.pool)
+        .await?;
+        if dry_run {
+            return Ok((matched as u64, 0));
+        }
+
+        let mut affected = 0;
+        loop {
+            let n = self.apply_batch().await?;
+            if n == 0 {
+                break;
+            }
+            affected += n;
+        }
+        Ok((matched as u64, affected))
+    }
+
+    // each batch is its own short transaction, rows locked by others are skipped instead of waited on
+    async fn apply_batch(&self) -> Result<u64> {
+        let mut tx = self.pool.begin().await?;
+        let emails: Vec<String> = sqlx::query_scalar(&format!(
+            "select email from user_stats where {} limit $2 for update skip locked",
+            INACTIVE
+        ))
+        .bind(self.config.inactive_days as i32)
+        .bind(self.config.batch_size as i64)
+        .fetch_all(&mut *tx)
+        .await?;
+        if emails.is_empty() {
+            return Ok(0);
+        }
+
+        let ret = match self.config.action {
+            RetentionAction::Delete => {
+                sqlx::query("delete from user_stats where email = any($1)")
+                    .bind(&emails)
+                    .execute(&mut *tx)
+                    .await?
+            }
+            RetentionAction::Anonymize => {
+                sqlx::query("delete from devices where email = any($1)")
+                    .bind(&emails)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query("delete from channel_consents where email = any($1)")
+                    .bind(&emails)
+                    .execute(&mut *tx)
+                    .await?;
+                sqlx::query(
+                    r#"update user_stats set email = 'anonymized-' || md5(email) || '@invalid', name = 'anonymized',
+                    gender = 'unknown', phone = null, attributes = '{}', anonymized_at = now()
+                    where email = any($1)"#,
+                )
+                .bind(&emails)
+                .execute(&mut *tx)
+                .await?
+            }
+        };
+        tx.commit().await?;
+        Ok(ret.rows_affected())
+    }
+}
@@ -7,13 +7,17 @@ use tokio::time::sleep;
 use anyhow::Result;
 
 use futures::StreamExt as _;
-use tonic::transport::Server;
+use tonic::transport::{self, Server};
 use user_state::{
     pb::{
-        user_stats_client::UserStatsClient, IdQuery, QueryRequestBuilder, RawQueryRequestBuilder,
-        TimeQuery,
+        attribute_query::Predicate, attribute_value::Value, user_stats_client::UserStatsClient,
+        AttributeQuery, AttributeValue, Channel, ChurnRisk, EngagementQueryBuilder,
+        GetConsentsRequest, GetHistoryRequest, IdQuery, ListDevicesRequest, NumberRange, Platform,
+        QueryRequestBuilder, RawQueryRequestBuilder, RefreshEngagementRequest,
+        RegisterDeviceRequest, SetAttributesRequestBuilder, SetLocaleRequest, SetPhoneRequest,
+        TimeQuery, UnregisterDeviceRequest, UpdateConsentRequest,
     },
-    AppConfig, UserStatsService,
+    AppConfig, RetentionAction, RetentionConfig, RetentionJob, UserStatsService,
 };
 
 #[tokio::test]
@@ -71,6 +75,288 @@ async fn query_should_work() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn engagement_query_should_work() -> Result<()> {
+    let addr = start_server(50056).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let ret = client
+        .refresh_engagement(RefreshEngagementRequest {})
+        .await?
+        .into_inner();
+    assert!(ret.updated > 0);
+
+    let engagement = EngagementQueryBuilder::default()
+        .min_score(10.0)
+        .churn_risks(vec![ChurnRisk::High as i32])
+        .build()?;
+    let query = QueryRequestBuilder::default()
+        .engagement(engagement)
+        .build()?;
+    let stream = client.query(query).await?.into_inner();
+    let ret = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    assert!(!ret.is_empty());
+
+    let engagement = EngagementQueryBuilder::default()
+        .min_score(f32::NAN)
+        .build()?;
+    let query = QueryRequestBuilder::default()
+        .engagement(engagement)
+        .build()?;
+    let ret = client.query(query).await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::InvalidArgument);
+    Ok(())
+}
+
+#[tokio::test]
+async fn attributes_query_should_work() -> Result<()> {
+    let addr = start_server(50055).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let email = first_email(&mut client).await?;
+    let req = SetAttributesRequestBuilder::default()
+        .email(email.clone())
+        .attribute(("plan".to_string(), attr(Value::StringValue("pro".into()))))
+        .attribute(("seats".to_string(), attr(Value::NumberValue(12.0))))
+        .attribute(("beta".to_string(), attr(Value::BoolValue(true))))
+        .build()?;
+    client.set_attributes(req).await?;
+
+    let query = QueryRequestBuilder::default()
+        .attribute(("plan".to_string(), pred(Predicate::Equals("pro".into()))))
+        .attribute((
+            "seats".to_string(),
+            pred(Predicate::Range(NumberRange {
+                min: Some(10.0),
+                max: Some(20.0),
+            })),
+        ))
+        .attribute(("beta".to_string(), pred(Predicate::Is(true))))
+        .attribute(("country".to_string(), pred(Predicate::Exists(false))))
+        .build()?;
+    let stream = client.query(query).await?.into_inner();
+    let ret = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    assert!(ret.iter().any(|u| u.email == email));
+
+    let query = QueryRequestBuilder::default()
+        .attribute((
+            "seats".to_string(),
+            pred(Predicate::Range(NumberRange {
+                min: None,
+                max: Some(f64::INFINITY),
+            })),
+        ))
+        .build()?;
+    let ret = client.query(query).await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::InvalidArgument);
+    Ok(())
+}
+
+#[tokio::test]
+async fn devices_should_work() -> Result<()> {
+    let addr = start_server(50054).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let email = first_email(&mut client).await?;
+    for (id, platform) in [("test-ios", Platform::Ios), ("test-web", Platform::Web)] {
+        let req = RegisterDeviceRequest {
+            id: id.to_string(),
+            email: email.clone(),
+            platform: platform as i32,
+        };
+        let device = client.register_device(req).await?.into_inner();
+        assert_eq!(device.platform(), platform);
+    }
+    let ret = client
+        .list_devices(ListDevicesRequest {
+            email: email.clone(),
+        })
+        .await?
+        .into_inner();
+    assert!(ret.devices.iter().any(|d| d.id == "test-ios"));
+
+    let req = SetAttributesRequestBuilder::default()
+        .email(email.clone())
+        .attribute(("device_test".to_string(), attr(Value::BoolValue(true))))
+        .build()?;
+    client.set_attributes(req).await?;
+    let query = QueryRequestBuilder::default()
+        .with_devices(true)
+        .attribute(("device_test".to_string(), pred(Predicate::Is(true))))
+        .build()?;
+    let stream = client.query(query).await?.into_inner();
+    let users = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    // other runs may have tagged other users, look for this one
+    let user = users
+        .iter()
+        .find(|u| u.email == email)
+        .expect("no user found");
+    assert!(user.device_ids.contains(&"test-ios".to_string()));
+
+    client
+        .unregister_device(UnregisterDeviceRequest {
+            id: "test-web".to_string(),
+        })
+        .await?;
+    let ret = client
+        .list_devices(ListDevicesRequest { email })
+        .await?
+        .into_inner();
+    assert!(ret.devices.iter().all(|d| d.id != "test-web"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn consents_should_work() -> Result<()> {
+    let addr = start_server(50060).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let email = first_email(&mut client).await?;
+    let ret = client
+        .set_phone(SetPhoneRequest {
+            email: email.clone(),
+            phone: "13800138000".to_string(),
+        })
+        .await;
+    assert!(ret.is_err());
+    client
+        .set_phone(SetPhoneRequest {
+            email: email.clone(),
+            phone: "+8613800138000".to_string(),
+        })
+        .await?;
+    for (channel, opted_in) in [(Channel::Sms, true), (Channel::Email, false)] {
+        let req = UpdateConsentRequest {
+            email: email.clone(),
+            channel: channel as i32,
+            opted_in,
+            source: "test".to_string(),
+        };
+        client.update_consent(req).await?;
+    }
+    let ret = client
+        .get_consents(GetConsentsRequest {
+            email: email.clone(),
+        })
+        .await?
+        .into_inner();
+    assert_eq!(ret.consents.len(), 2);
+
+    let query = QueryRequestBuilder::default()
+        .consented_channel(Channel::Sms as i32)
+        .build()?;
+    let stream = client.query(query).await?.into_inner();
+    let ret = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    let user = ret
+        .iter()
+        .find(|u| u.email == email)
+        .expect("user not found");
+    assert_eq!(user.phone.as_deref(), Some("+8613800138000"));
+
+    let query = QueryRequestBuilder::default()
+        .consented_channel(Channel::Email as i32)
+        .build()?;
+    let stream = client.query(query).await?.into_inner();
+    let ret = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    assert!(ret.iter().all(|u| u.email != email));
+    Ok(())
+}
+
+#[tokio::test]
+async fn get_history_should_work() -> Result<()> {
+    let addr = start_server(50061).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let email = first_email(&mut client).await?;
+    let history = client
+        .get_history(GetHistoryRequest {
+            email: email.clone(),
+        })
+        .await?
+        .into_inner();
+    assert_eq!(history.email, email);
+    assert!(!history.finished.is_empty());
+
+    let ret = client
+        .get_history(GetHistoryRequest {
+            email: "nobody@example.com".to_string(),
+        })
+        .await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::NotFound);
+    Ok(())
+}
+
+#[tokio::test]
+async fn set_locale_should_work() -> Result<()> {
+    let addr = start_server(50059).await?;
+    let addr = format!("http://{}", addr);
+    let mut client = UserStatsClient::connect(addr).await?;
+    let email = first_email(&mut client).await?;
+    let ret = client
+        .set_locale(SetLocaleRequest {
+            email: email.clone(),
+            locale: "chinese".to_string(),
+        })
+        .await;
+    assert_eq!(ret.unwrap_err().code(), tonic::Code::InvalidArgument);
+    let ret = client
+        .set_locale(SetLocaleRequest {
+            email: email.clone(),
+            locale: "zh_cn".to_string(),
+        })
+        .await?
+        .into_inner();
+    assert_eq!(ret.locale, "zh-CN");
+
+    let query = QueryRequestBuilder::default().build()?;
+    let stream = client.query(query).await?.into_inner();
+    let ret = stream
+        .then(|response| async move { response.unwrap() })
+        .collect::<Vec<_>>()
+        .await;
+    let user = ret
+        .iter()
+        .find(|u| u.email == email)
+        .expect("user not found");
+    assert_eq!(user.locale.as_deref(), Some("zh-CN"));
+    Ok(())
+}
+
+async fn first_email(client: &mut UserStatsClient<transport::Channel>) -> Result<String> {
+    let query = RawQueryRequestBuilder::default()
+        .query(
+            "SELECT email, name FROM user_stats where anonymized_at is null order by email limit 1",
+        )
+        .build()?;
+    let mut stream = client.raw_query(query).await?.into_inner();
+    let user = stream.next().await.expect("no user found")?;
+    Ok(user.email)
+}
+
+fn attr(value: Value) -> AttributeValue {
+    AttributeValue { value: Some(value) }
+}
+fn pred(predicate: Predicate) -> AttributeQuery {
+    AttributeQuery {
+        predicate: Some(predicate),
+    }
+}
+
 fn to_ids(ids: &[u32]) -> IdQuery {
     IdQuery { ids: ids.to_vec() }
 }
@@ -89,3 +375,19 @@ fn to_ts(days: i64) -> Timestamp {
         nanos: ts.timestamp_subsec_nanos() as _,
     }
 }
+
+#[tokio::test]
+async fn retention_dry_run_should_work() -> Result<()> {
+    let config = AppConfig::try_load()?;
+    let retention = RetentionConfig {
+        inactive_days: 30,
+        action: RetentionAction::Delete,
+        batch_size: 100,
+        interval: 0,
+    };
+    let job = RetentionJob::try_new(&config.server.db_url, retention).await?;
+    let report = job.run(true).await?;
+    assert!(report.matched > 0);
+    assert_eq!(report.affected, 0);
+    Ok(())
+}